@@ -0,0 +1,323 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Display;
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::time::Duration;
+use tracing::Level;
+
+/// Semua setting yang hilang atau tidak valid, dikumpulkan supaya bisa diperbaiki sekaligus.
+#[derive(Debug, Default)]
+pub struct ConfigError {
+    pub errors: Vec<String>,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "invalid configuration:")?;
+        for error in &self.errors {
+            writeln!(f, "  - {}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Membaca setting berlapis: default di kode, lalu file TOML dari `CONFIG_FILE`, lalu `.env`, lalu environment.
+/// Key di file TOML diratakan jadi nama env, misalnya `[redis] timeout_ms` dibaca sebagai `REDIS_TIMEOUT_MS`.
+#[derive(Debug, Default)]
+pub struct ConfigLoader {
+    values: HashMap<String, String>,
+    errors: Vec<String>,
+}
+
+impl ConfigLoader {
+    pub fn load() -> Self {
+        let dotenv: Vec<(String, String)> = dotenvy::dotenv_iter()
+            .map(|iter| iter.filter_map(Result::ok).collect())
+            .unwrap_or_default();
+        let env = std::env::vars_os()
+            .filter_map(|(key, value)| Some((key.into_string().ok()?, value.into_string().ok()?)));
+        Self::from_layers(dotenv, env)
+    }
+
+    /// `.env` tidak menimpa variabel yang sudah di-set di environment, dan `CONFIG_FILE` boleh berasal dari keduanya.
+    fn from_layers(dotenv: impl IntoIterator<Item = (String, String)>, env: impl IntoIterator<Item = (String, String)>) -> Self {
+        let mut overrides: HashMap<String, String> = dotenv.into_iter().collect();
+        overrides.extend(env);
+
+        let mut loader = ConfigLoader::default();
+        if let Some(path) = overrides.get("CONFIG_FILE").filter(|path| !path.trim().is_empty()) {
+            match std::fs::read_to_string(path) {
+                Ok(content) => match content.parse::<toml::Table>() {
+                    Ok(table) => flatten("", &table, &mut loader.values),
+                    Err(error) => loader.errors.push(format!("CONFIG_FILE {} is not valid TOML: {}", path, error.message())),
+                },
+                Err(error) => loader.errors.push(format!("CONFIG_FILE {} cannot be read: {}", path, error)),
+            }
+        }
+        loader.values.extend(overrides);
+        loader
+    }
+
+    /// Nilai kosong dianggap tidak di-set.
+    pub fn string(&self, key: &str) -> Option<String> {
+        self.values.get(key).filter(|value| !value.trim().is_empty()).map(|value| value.trim().to_string())
+    }
+
+    pub fn optional<T>(&mut self, key: &str) -> Option<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value = self.string(key)?;
+        match value.parse() {
+            Ok(parsed) => Some(parsed),
+            Err(error) => {
+                self.errors.push(format!("{} has invalid value {:?}: {}", key, value, error));
+                None
+            }
+        }
+    }
+
+    pub fn required<T>(&mut self, key: &str) -> Option<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        if self.string(key).is_none() {
+            self.errors.push(format!("{} is required", key));
+            return None;
+        }
+        self.optional(key)
+    }
+
+    pub fn or<T>(&mut self, key: &str, default: T) -> T
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.optional(key).unwrap_or(default)
+    }
+
+    pub fn millis(&mut self, key: &str, default: u64) -> Duration {
+        Duration::from_millis(self.or(key, default))
+    }
+
+    pub fn secs(&mut self, key: &str, default: u64) -> Duration {
+        Duration::from_secs(self.or(key, default))
+    }
+
+    /// Daftar dipisah koma; satu item yang tidak valid membuat seluruh setting ditolak.
+    pub fn list<T>(&mut self, key: &str, default: Vec<T>) -> Vec<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let Some(value) = self.string(key) else {
+            return default;
+        };
+        let parsed: Result<Vec<T>, String> = value.split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| item.parse().map_err(|error| format!("{} has invalid item {:?}: {}", key, item, error)))
+            .collect();
+        match parsed {
+            Ok(items) => items,
+            Err(error) => {
+                self.errors.push(error);
+                default
+            }
+        }
+    }
+
+    /// Untuk aturan yang melibatkan lebih dari satu setting.
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.errors.push(message.into());
+    }
+
+    pub fn finish<T>(self, config: T) -> Result<T, ConfigError> {
+        if self.errors.is_empty() {
+            Ok(config)
+        } else {
+            Err(ConfigError { errors: self.errors })
+        }
+    }
+}
+
+fn flatten(prefix: &str, table: &toml::Table, values: &mut HashMap<String, String>) {
+    for (key, value) in table {
+        let name = if prefix.is_empty() {
+            key.to_ascii_uppercase()
+        } else {
+            format!("{}_{}", prefix, key.to_ascii_uppercase())
+        };
+        match value {
+            toml::Value::Table(table) => flatten(&name, table, values),
+            toml::Value::String(value) => {
+                values.insert(name, value.clone());
+            }
+            toml::Value::Array(items) => {
+                let items: Vec<String> = items.iter()
+                    .map(|item| item.as_str().map(str::to_string).unwrap_or_else(|| item.to_string()))
+                    .collect();
+                values.insert(name, items.join(","));
+            }
+            value => {
+                values.insert(name, value.to_string());
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DatabaseConfig {
+    pub url: String,
+    pub max_connections: u32,
+    pub min_connections: u32,
+    pub acquire_timeout: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct RedisConfig {
+    pub url: String,
+    pub timeout: Duration,
+}
+
+/// Setting yang dipakai semua service. Setting khusus service dibaca dari loader yang sama sebelum `finish`.
+#[derive(Debug, Clone)]
+pub struct ServiceConfig {
+    pub bind_address: SocketAddr,
+    pub log_level: Level,
+    pub health_check_timeout: Duration,
+    pub shutdown_drain_delay: Duration,
+    pub shutdown_grace_period: Duration,
+    pub database: DatabaseConfig,
+    pub redis: Option<RedisConfig>,
+}
+
+impl ServiceConfig {
+    pub fn from_loader(loader: &mut ConfigLoader) -> Self {
+        let database = DatabaseConfig {
+            url: loader.required("DATABASE_URL").unwrap_or_default(),
+            max_connections: loader.or("DATABASE_MAX_CONNECTIONS", 10),
+            min_connections: loader.or("DATABASE_MIN_CONNECTIONS", 0),
+            acquire_timeout: loader.millis("DATABASE_ACQUIRE_TIMEOUT_MS", 30_000),
+        };
+        if database.max_connections == 0 {
+            loader.error("DATABASE_MAX_CONNECTIONS must be greater than 0");
+        }
+        if database.min_connections > database.max_connections {
+            loader.error("DATABASE_MIN_CONNECTIONS must not be greater than DATABASE_MAX_CONNECTIONS");
+        }
+
+        let redis_timeout = loader.millis("REDIS_TIMEOUT_MS", 200);
+        let redis = loader.string("REDIS_URL").map(|url| RedisConfig { url, timeout: redis_timeout });
+        if redis.as_ref().is_some_and(|redis| !["redis://", "rediss://", "unix://", "redis+unix://"].iter().any(|scheme| redis.url.starts_with(scheme))) {
+            loader.error("REDIS_URL must start with redis://, rediss:// or unix://");
+        }
+
+        ServiceConfig {
+            bind_address: loader.or("BIND_ADDRESS", SocketAddr::from(([0, 0, 0, 0], 3000))),
+            log_level: loader.or("LOG_LEVEL", Level::INFO),
+            health_check_timeout: loader.millis("HEALTH_CHECK_TIMEOUT_MS", 1000),
+            shutdown_drain_delay: loader.secs("SHUTDOWN_DRAIN_DELAY_SECS", 5),
+            shutdown_grace_period: loader.secs("SHUTDOWN_GRACE_PERIOD_SECS", 30),
+            database,
+            redis,
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn pairs(items: &[(&str, &str)]) -> Vec<(String, String)> {
+        items.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect()
+    }
+
+    fn config_file(name: &str, content: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("config-loader-{}-{}.toml", name, std::process::id()));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn defaults_apply_when_nothing_is_set() {
+        let mut loader = ConfigLoader::from_layers(Vec::new(), Vec::new());
+        assert_eq!(loader.or("DATABASE_MAX_CONNECTIONS", 10u32), 10);
+        assert_eq!(loader.millis("REDIS_TIMEOUT_MS", 200), Duration::from_millis(200));
+        assert!(loader.finish(()).is_ok());
+    }
+
+    #[test]
+    fn layers_override_in_order() {
+        let path = config_file("layers", "log_level = \"warn\"\nbind_address = \"127.0.0.1:4000\"\nregion = \"toml\"\n");
+        let config_file = path.to_string_lossy().to_string();
+        let mut loader = ConfigLoader::from_layers(
+            pairs(&[("CONFIG_FILE", &config_file), ("BIND_ADDRESS", "127.0.0.1:5000"), ("REGION", "dotenv")]),
+            pairs(&[("REGION", "env")]),
+        );
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(loader.or("LOG_LEVEL", Level::INFO), Level::WARN);
+        assert_eq!(loader.or("BIND_ADDRESS", SocketAddr::from(([0, 0, 0, 0], 3000))), SocketAddr::from(([127, 0, 0, 1], 5000)));
+        assert_eq!(loader.string("REGION").as_deref(), Some("env"));
+        assert_eq!(loader.or("HEALTH_CHECK_TIMEOUT_MS", 1000u64), 1000);
+    }
+
+    #[test]
+    fn config_file_can_come_from_env() {
+        let path = config_file("env", "region = \"toml\"\n");
+        let loader = ConfigLoader::from_layers(Vec::new(), pairs(&[("CONFIG_FILE", &path.to_string_lossy())]));
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(loader.string("REGION").as_deref(), Some("toml"));
+    }
+
+    #[test]
+    fn nested_tables_and_arrays_are_flattened() {
+        let path = config_file("nested", "[redis]\ntimeout_ms = 300\n[database.pool]\nmax = 4\n[storage]\nthumbnail_sizes = [200, 400]\nbackends = [\"s3\", \"local\"]\n");
+        let mut loader = ConfigLoader::from_layers(Vec::new(), pairs(&[("CONFIG_FILE", &path.to_string_lossy())]));
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(loader.millis("REDIS_TIMEOUT_MS", 200), Duration::from_millis(300));
+        assert_eq!(loader.or("DATABASE_POOL_MAX", 10u32), 4);
+        assert_eq!(loader.list("STORAGE_THUMBNAIL_SIZES", Vec::<u32>::new()), vec![200, 400]);
+        assert_eq!(loader.string("STORAGE_BACKENDS").as_deref(), Some("s3,local"));
+    }
+
+    #[test]
+    fn list_parses_items_and_rejects_invalid_ones() {
+        let mut loader = ConfigLoader::from_layers(Vec::new(), pairs(&[("SIZES", " 100, 200 ,,300 "), ("BROKEN", "100,big")]));
+        assert_eq!(loader.list("SIZES", vec![1u32]), vec![100, 200, 300]);
+        assert_eq!(loader.list("MISSING", vec![1u32]), vec![1]);
+        assert_eq!(loader.list("BROKEN", vec![1u32]), vec![1]);
+
+        let error = loader.finish(()).unwrap_err();
+        assert_eq!(error.errors.len(), 1);
+        assert!(error.errors[0].starts_with("BROKEN has invalid item \"big\""));
+    }
+
+    #[test]
+    fn missing_required_keys_are_collected() {
+        let mut loader = ConfigLoader::from_layers(Vec::new(), pairs(&[("BLANK", "  ")]));
+        assert_eq!(loader.required::<String>("DATABASE_URL"), None);
+        assert_eq!(loader.required::<String>("BLANK"), None);
+        assert_eq!(loader.optional::<u32>("PORT"), None);
+
+        let error = loader.finish(()).unwrap_err();
+        assert_eq!(error.errors, vec!["DATABASE_URL is required".to_string(), "BLANK is required".to_string()]);
+    }
+
+    #[test]
+    fn invalid_config_file_is_reported() {
+        let path = config_file("invalid", "this is not toml");
+        let loader = ConfigLoader::from_layers(Vec::new(), pairs(&[("CONFIG_FILE", &path.to_string_lossy())]));
+        std::fs::remove_file(path).unwrap();
+
+        let error = loader.finish(()).unwrap_err();
+        assert!(error.errors[0].contains("is not valid TOML"));
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Extension, Router};
+use http::StatusCode;
+use serde::Serialize;
+use crate::response::{BaseApiResponse, ErrorDetails};
+
+type CheckFuture = Pin<Box<dyn Future<Output = Result<(), String>> + Send>>;
+type CheckFn = Arc<dyn Fn() -> CheckFuture + Send + Sync>;
+
+#[derive(Clone)]
+struct DependencyCheck {
+    name: String,
+    required: bool,
+    check: CheckFn,
+}
+
+/// Status kesehatan service. Di-clone ke router health dan ke kode shutdown, jadi flag draining dibagi bersama.
+#[derive(Clone)]
+pub struct Health {
+    checks: Vec<DependencyCheck>,
+    timeout: Duration,
+    draining: Arc<AtomicBool>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DependencyStatus {
+    pub status: &'static str,
+    pub required: bool,
+    pub latency_ms: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ReadinessReport {
+    pub ready: bool,
+    pub draining: bool,
+    pub dependencies: BTreeMap<String, DependencyStatus>,
+}
+
+impl Health {
+    pub fn new(timeout: Duration) -> Self {
+        Health {
+            checks: Vec::new(),
+            timeout,
+            draining: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Dependency wajib: kalau gagal, readiness mengembalikan 503.
+    pub fn with_check<F, Fut>(self, name: &str, check: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        self.add(name, true, check)
+    }
+
+    /// Dependency opsional seperti cache: statusnya dilaporkan tapi tidak membuat service dianggap tidak siap.
+    pub fn with_optional_check<F, Fut>(self, name: &str, check: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        self.add(name, false, check)
+    }
+
+    fn add<F, Fut>(mut self, name: &str, required: bool, check: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        self.checks.push(DependencyCheck {
+            name: name.to_string(),
+            required,
+            check: Arc::new(move || Box::pin(check()) as CheckFuture),
+        });
+        self
+    }
+
+    pub fn set_draining(&self) {
+        self.draining.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    pub async fn readiness(&self) -> ReadinessReport {
+        // Semua dependency dicek bersamaan supaya total waktu probe tidak melebihi satu timeout
+        let checks = self.checks.iter().map(|dependency| async move {
+            let started = Instant::now();
+            let result = match tokio::time::timeout(self.timeout, (dependency.check)()).await {
+                Ok(result) => result,
+                Err(_) => Err(format!("timed out after {}ms", self.timeout.as_millis())),
+            };
+            let status = DependencyStatus {
+                status: if result.is_ok() { "up" } else { "down" },
+                required: dependency.required,
+                latency_ms: started.elapsed().as_millis(),
+                error: result.err(),
+            };
+            (dependency.name.clone(), status)
+        });
+        let dependencies: BTreeMap<String, DependencyStatus> = futures::future::join_all(checks).await
+            .into_iter()
+            .collect();
+
+        let draining = self.is_draining();
+        let healthy = dependencies.values().all(|dependency| !dependency.required || dependency.error.is_none());
+        ReadinessReport {
+            ready: healthy && !draining,
+            draining,
+            dependencies,
+        }
+    }
+}
+
+pub fn routes(health: Health) -> Router {
+    Router::new()
+        .route("/health/live", get(live))
+        .route("/health/ready", get(ready))
+        .layer(Extension(health))
+}
+
+// Liveness hanya memastikan proses masih melayani request, dependency tidak ikut dicek
+async fn live() -> impl IntoResponse {
+    BaseApiResponse::<(), ErrorDetails>::new(
+        "success",
+        "Service is alive",
+        None,
+        None,
+    ).with_status_code(StatusCode::OK)
+}
+
+async fn ready(Extension(health): Extension<Health>) -> impl IntoResponse {
+    let report = health.readiness().await;
+    if report.ready {
+        BaseApiResponse::<ReadinessReport, ErrorDetails>::new(
+            "success",
+            "Service is ready",
+            Some(report),
+            None,
+        ).with_status_code(StatusCode::OK)
+    } else {
+        let message = if report.draining { "Service is shutting down" } else { "Service is not ready" };
+        BaseApiResponse::<ReadinessReport, ErrorDetails>::new(
+            "error",
+            message,
+            Some(report),
+            Some(ErrorDetails {
+                code: "SERVICE_UNAVAILABLE".to_string(),
+                message: message.to_string(),
+            }),
+        ).with_status_code(StatusCode::SERVICE_UNAVAILABLE)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_millis(100);
+
+    #[tokio::test]
+    async fn ready_when_all_checks_pass() {
+        let health = Health::new(TIMEOUT)
+            .with_check("postgres", || async { Ok(()) })
+            .with_optional_check("redis", || async { Ok(()) });
+
+        let report = health.readiness().await;
+        assert!(report.ready);
+        assert!(!report.draining);
+        assert_eq!(report.dependencies["postgres"].status, "up");
+        assert_eq!(report.dependencies["redis"].status, "up");
+    }
+
+    #[tokio::test]
+    async fn not_ready_when_required_check_fails() {
+        let health = Health::new(TIMEOUT)
+            .with_check("postgres", || async { Err("connection refused".to_string()) })
+            .with_check("storage", || async { Ok(()) });
+
+        let report = health.readiness().await;
+        assert!(!report.ready);
+        assert_eq!(report.dependencies["postgres"].status, "down");
+        assert_eq!(report.dependencies["postgres"].error.as_deref(), Some("connection refused"));
+        assert_eq!(report.dependencies["storage"].status, "up");
+    }
+
+    #[tokio::test]
+    async fn optional_check_failure_keeps_service_ready() {
+        let health = Health::new(TIMEOUT)
+            .with_check("postgres", || async { Ok(()) })
+            .with_optional_check("redis", || async { Err("connection refused".to_string()) });
+
+        let report = health.readiness().await;
+        assert!(report.ready);
+        assert_eq!(report.dependencies["redis"].status, "down");
+        assert!(!report.dependencies["redis"].required);
+    }
+
+    #[tokio::test]
+    async fn slow_check_times_out() {
+        let health = Health::new(TIMEOUT)
+            .with_check("postgres", || async {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                Ok(())
+            });
+
+        let report = health.readiness().await;
+        assert!(!report.ready);
+        assert_eq!(report.dependencies["postgres"].error.as_deref(), Some("timed out after 100ms"));
+    }
+
+    #[tokio::test]
+    async fn draining_service_is_not_ready() {
+        let health = Health::new(TIMEOUT).with_check("postgres", || async { Ok(()) });
+        health.clone().set_draining();
+
+        let report = health.readiness().await;
+        assert!(!report.ready);
+        assert!(report.draining);
+        assert_eq!(report.dependencies["postgres"].status, "up");
+    }
+}
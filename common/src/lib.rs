@@ -1 +1,6 @@
-pub mod response;
\ No newline at end of file
+pub mod config;
+pub mod health;
+pub mod pagination;
+pub mod response;
+pub mod shutdown;
+pub mod validation;
\ No newline at end of file
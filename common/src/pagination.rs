@@ -0,0 +1,48 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+#[derive(Serialize, Debug)]
+pub struct Pagination {
+    pub next_cursor: Option<String>,
+    pub prev_cursor: Option<String>,
+    pub total: i64,
+}
+
+pub fn encode_cursor<T: Serialize>(cursor: &T) -> String {
+    let json = serde_json::to_vec(cursor).unwrap_or_default();
+    URL_SAFE_NO_PAD.encode(json)
+}
+
+pub fn decode_cursor<T: DeserializeOwned>(cursor: &str) -> Option<T> {
+    let json = URL_SAFE_NO_PAD.decode(cursor).ok()?;
+    serde_json::from_slice(&json).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct TestCursor {
+        value: String,
+        id: u64,
+    }
+
+    #[test]
+    fn cursor_round_trips() {
+        let cursor = TestCursor { value: "Kopi Susu".to_string(), id: 42 };
+        let encoded = encode_cursor(&cursor);
+        assert!(encoded.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_eq!(decode_cursor::<TestCursor>(&encoded), Some(cursor));
+    }
+
+    #[test]
+    fn malformed_cursor_is_rejected() {
+        assert_eq!(decode_cursor::<TestCursor>("not base64!"), None);
+        assert_eq!(decode_cursor::<TestCursor>(&URL_SAFE_NO_PAD.encode("not json")), None);
+        assert_eq!(decode_cursor::<TestCursor>(&encode_cursor(&"wrong shape")), None);
+    }
+}
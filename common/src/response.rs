@@ -3,6 +3,8 @@ use axum::response::{IntoResponse, Response};
 use http::StatusCode;
 use serde::Serialize;
 use thiserror::Error;
+use crate::pagination::Pagination;
+use crate::validation::FieldError;
 
 
 #[derive(Serialize, Debug)]
@@ -11,6 +13,13 @@ pub struct ErrorDetails {
     pub message: String,
 }
 
+#[derive(Serialize, Debug)]
+pub struct ValidationErrorDetails {
+    pub code: String,
+    pub message: String,
+    pub fields: Vec<FieldError>,
+}
+
 #[derive(Serialize, Debug)]
 pub struct BaseApiResponse<T, E>
 where
@@ -21,6 +30,8 @@ where
     pub message: String,
     pub data: Option<T>,
     pub errors: Option<E>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<Pagination>,
 }
 
 impl<T, E> BaseApiResponse<T, E>
@@ -34,9 +45,15 @@ where
             message: message.to_string(),
             data,
             errors,
+            pagination: None,
         }
     }
 
+    pub fn with_pagination(mut self, pagination: Pagination) -> Self {
+        self.pagination = Some(pagination);
+        self
+    }
+
     pub fn with_status_code(self, code: StatusCode) -> Response {
         let mut response = Json(self).into_response(); // Convert to a full response
         *response.status_mut() = code; // Modify status code on the entire response object
@@ -0,0 +1,50 @@
+use std::fmt;
+use serde::Serialize;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+/// Kumpulan error validasi per field, dikumpulkan dulu supaya client menerima semua kesalahan sekaligus.
+#[derive(Debug, Default)]
+pub struct ValidationErrors {
+    pub fields: Vec<FieldError>,
+}
+
+impl ValidationErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, field: &str, message: &str) {
+        self.fields.push(FieldError {
+            field: field.to_string(),
+            message: message.to_string(),
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    pub fn into_result(self) -> Result<(), ValidationErrors> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields: Vec<String> = self.fields.iter()
+            .map(|error| format!("{}: {}", error.field, error.message))
+            .collect();
+        write!(f, "validation failed: {}", fields.join("; "))
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
\ No newline at end of file
@@ -0,0 +1,4 @@
+// Build ulang saat isi folder migrations berubah, karena sqlx::migrate! menyematkannya ke binary
+fn main() {
+    println!("cargo:rerun-if-changed=migrations");
+}
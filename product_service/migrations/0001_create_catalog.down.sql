@@ -0,0 +1,3 @@
+DROP TABLE product_reviews;
+DROP TABLE products;
+DROP TABLE categories;
@@ -0,0 +1,4 @@
+DROP INDEX idx_products_description_trgm;
+DROP INDEX idx_products_name_trgm;
+DROP INDEX idx_products_search_vector;
+ALTER TABLE products DROP COLUMN search_vector;
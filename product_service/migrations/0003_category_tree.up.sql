@@ -0,0 +1,2 @@
+ALTER TABLE categories ADD COLUMN parent_id UUID REFERENCES categories (id) ON DELETE RESTRICT;
+CREATE INDEX idx_categories_parent_id ON categories (parent_id);
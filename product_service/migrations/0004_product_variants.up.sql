@@ -0,0 +1,22 @@
+CREATE TABLE product_option_types (
+    id UUID PRIMARY KEY,
+    product_id UUID NOT NULL REFERENCES products (id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    option_values TEXT[] NOT NULL,
+    created_at BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL,
+    UNIQUE (product_id, name)
+);
+
+CREATE TABLE product_variants (
+    id UUID PRIMARY KEY,
+    product_id UUID NOT NULL REFERENCES products (id) ON DELETE CASCADE,
+    sku VARCHAR(100) NOT NULL UNIQUE,
+    price NUMERIC(12, 2),
+    stock INTEGER NOT NULL DEFAULT 0 CHECK (stock >= 0),
+    barcode VARCHAR(32),
+    options JSONB NOT NULL,
+    created_at BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL,
+    UNIQUE (product_id, options)
+);
@@ -0,0 +1,16 @@
+CREATE TABLE product_images (
+    id UUID PRIMARY KEY,
+    product_id UUID NOT NULL REFERENCES products (id) ON DELETE CASCADE,
+    storage_key VARCHAR(512) NOT NULL,
+    url VARCHAR(1024) NOT NULL,
+    thumbnails JSONB NOT NULL,
+    content_type VARCHAR(100) NOT NULL,
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    is_primary BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL
+);
+CREATE INDEX idx_product_images_product_id ON product_images (product_id, position);
+CREATE UNIQUE INDEX idx_product_images_primary ON product_images (product_id) WHERE is_primary;
@@ -0,0 +1,3 @@
+DROP INDEX idx_products_active;
+ALTER TABLE products DROP COLUMN deleted_at;
+ALTER TABLE categories DROP COLUMN deleted_at;
@@ -0,0 +1,3 @@
+ALTER TABLE categories ADD COLUMN deleted_at BIGINT;
+ALTER TABLE products ADD COLUMN deleted_at BIGINT;
+CREATE INDEX idx_products_active ON products (created_at, id) WHERE deleted_at IS NULL;
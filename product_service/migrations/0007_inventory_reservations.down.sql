@@ -0,0 +1,2 @@
+DROP TABLE inventory_reservations;
+ALTER TABLE products DROP CONSTRAINT products_stock_non_negative;
@@ -0,0 +1,13 @@
+ALTER TABLE products ADD CONSTRAINT products_stock_non_negative CHECK (stock >= 0);
+
+CREATE TABLE inventory_reservations (
+    id UUID PRIMARY KEY,
+    product_id UUID NOT NULL REFERENCES products (id) ON DELETE CASCADE,
+    quantity INTEGER NOT NULL CHECK (quantity > 0),
+    reference VARCHAR(100) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'active',
+    expires_at BIGINT NOT NULL,
+    created_at BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL
+);
+CREATE INDEX idx_inventory_reservations_active ON inventory_reservations (product_id, expires_at) WHERE status = 'active';
@@ -0,0 +1,2 @@
+DROP TABLE stock_movements;
+DROP FUNCTION prevent_stock_movement_changes();
@@ -0,0 +1,26 @@
+CREATE TABLE stock_movements (
+    id UUID PRIMARY KEY,
+    product_id UUID NOT NULL REFERENCES products (id),
+    reason VARCHAR(20) NOT NULL CHECK (reason IN ('restock', 'sale', 'return', 'damage', 'correction')),
+    quantity INTEGER NOT NULL CHECK (quantity <> 0),
+    balance_after INTEGER NOT NULL CHECK (balance_after >= 0),
+    actor VARCHAR(100) NOT NULL,
+    reference VARCHAR(100),
+    created_at BIGINT NOT NULL
+);
+CREATE INDEX idx_stock_movements_product_id ON stock_movements (product_id, created_at DESC, id DESC);
+
+CREATE FUNCTION prevent_stock_movement_changes() RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION 'stock_movements is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER stock_movements_append_only
+    BEFORE UPDATE OR DELETE ON stock_movements
+    FOR EACH ROW EXECUTE FUNCTION prevent_stock_movement_changes();
+
+-- Saldo awal untuk produk yang sudah ada supaya total ledger sama dengan stock
+INSERT INTO stock_movements (id, product_id, reason, quantity, balance_after, actor, reference, created_at)
+SELECT gen_random_uuid(), id, 'correction', stock, stock, 'system', 'opening-balance', (extract(epoch from now()) * 1000)::bigint
+FROM products WHERE stock <> 0;
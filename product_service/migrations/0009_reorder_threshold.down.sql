@@ -0,0 +1,2 @@
+DROP INDEX idx_products_low_stock;
+ALTER TABLE products DROP COLUMN reorder_threshold;
@@ -0,0 +1,2 @@
+ALTER TABLE products ADD COLUMN reorder_threshold INTEGER NOT NULL DEFAULT 0 CHECK (reorder_threshold >= 0);
+CREATE INDEX idx_products_low_stock ON products ((stock - reorder_threshold)) WHERE reorder_threshold > 0 AND deleted_at IS NULL;
@@ -0,0 +1,20 @@
+CREATE TABLE product_prices (
+    id UUID PRIMARY KEY,
+    product_id UUID NOT NULL REFERENCES products (id),
+    price NUMERIC(12, 2) NOT NULL CHECK (price >= 0),
+    previous_price NUMERIC(12, 2),
+    effective_from BIGINT NOT NULL,
+    effective_until BIGINT,
+    actor VARCHAR(100) NOT NULL,
+    status VARCHAR(20) NOT NULL CHECK (status IN ('scheduled', 'applied', 'ended', 'cancelled')),
+    created_at BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL,
+    CHECK (effective_until IS NULL OR effective_until > effective_from)
+);
+CREATE INDEX idx_product_prices_product_id ON product_prices (product_id, effective_from DESC);
+CREATE INDEX idx_product_prices_pending ON product_prices (effective_from) WHERE status = 'scheduled';
+CREATE INDEX idx_product_prices_ending ON product_prices (effective_until) WHERE status = 'applied' AND effective_until IS NOT NULL;
+
+-- Harga yang berlaku sekarang jadi entri pertama di riwayat
+INSERT INTO product_prices (id, product_id, price, effective_from, actor, status, created_at, updated_at)
+SELECT gen_random_uuid(), id, price, created_at, 'system', 'applied', created_at, created_at FROM products;
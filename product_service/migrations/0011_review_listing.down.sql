@@ -0,0 +1,2 @@
+DROP INDEX idx_product_reviews_product_id;
+ALTER TABLE product_reviews DROP COLUMN helpful_count;
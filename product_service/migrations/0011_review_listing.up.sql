@@ -0,0 +1,2 @@
+ALTER TABLE product_reviews ADD COLUMN helpful_count INTEGER NOT NULL DEFAULT 0;
+CREATE INDEX idx_product_reviews_product_id ON product_reviews (product_id, created_at DESC, id DESC);
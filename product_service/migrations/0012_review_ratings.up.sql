@@ -0,0 +1,24 @@
+CREATE TABLE product_ratings (
+    product_id UUID PRIMARY KEY REFERENCES products (id),
+    review_count INTEGER NOT NULL DEFAULT 0,
+    rating_sum INTEGER NOT NULL DEFAULT 0,
+    rating_1 INTEGER NOT NULL DEFAULT 0,
+    rating_2 INTEGER NOT NULL DEFAULT 0,
+    rating_3 INTEGER NOT NULL DEFAULT 0,
+    rating_4 INTEGER NOT NULL DEFAULT 0,
+    rating_5 INTEGER NOT NULL DEFAULT 0,
+    rating_average NUMERIC(3, 2) GENERATED ALWAYS AS (
+        CASE WHEN rating_1 + rating_2 + rating_3 + rating_4 + rating_5 > 0
+        THEN ROUND(rating_sum::numeric / (rating_1 + rating_2 + rating_3 + rating_4 + rating_5), 2) END
+    ) STORED,
+    updated_at BIGINT NOT NULL
+);
+CREATE INDEX idx_product_ratings_average ON product_ratings (rating_average);
+
+INSERT INTO product_ratings (product_id, review_count, rating_sum, rating_1, rating_2, rating_3, rating_4, rating_5, updated_at)
+SELECT product_id, COUNT(*),
+    COALESCE(SUM(rating) FILTER (WHERE rating BETWEEN 1 AND 5), 0),
+    COUNT(*) FILTER (WHERE rating = 1), COUNT(*) FILTER (WHERE rating = 2), COUNT(*) FILTER (WHERE rating = 3),
+    COUNT(*) FILTER (WHERE rating = 4), COUNT(*) FILTER (WHERE rating = 5),
+    (extract(epoch from now()) * 1000)::bigint
+FROM product_reviews GROUP BY product_id;
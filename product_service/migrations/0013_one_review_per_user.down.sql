@@ -0,0 +1,21 @@
+ALTER TABLE product_reviews DROP CONSTRAINT product_reviews_product_user_key;
+ALTER TABLE product_reviews DROP CONSTRAINT product_reviews_rating_range;
+
+-- Ulasan dari arsip dikembalikan; produk yang sudah dihapus sejak migrasi naik dilewati
+UPDATE product_reviews SET rating = archive.rating
+FROM product_reviews_archive archive
+WHERE archive.id = product_reviews.id AND archive.archive_reason = 'rating_out_of_range';
+INSERT INTO product_reviews (id, product_id, user_id, comment, rating, created_at, updated_at, helpful_count)
+SELECT archive.id, archive.product_id, archive.user_id, archive.comment, archive.rating, archive.created_at, archive.updated_at, archive.helpful_count
+FROM product_reviews_archive archive JOIN products ON products.id = archive.product_id
+WHERE archive.archive_reason = 'duplicate';
+DROP TABLE product_reviews_archive;
+
+DELETE FROM product_ratings;
+INSERT INTO product_ratings (product_id, review_count, rating_sum, rating_1, rating_2, rating_3, rating_4, rating_5, updated_at)
+SELECT product_id, COUNT(*),
+    COALESCE(SUM(rating) FILTER (WHERE rating BETWEEN 1 AND 5), 0),
+    COUNT(*) FILTER (WHERE rating = 1), COUNT(*) FILTER (WHERE rating = 2), COUNT(*) FILTER (WHERE rating = 3),
+    COUNT(*) FILTER (WHERE rating = 4), COUNT(*) FILTER (WHERE rating = 5),
+    (extract(epoch from now()) * 1000)::bigint
+FROM product_reviews GROUP BY product_id;
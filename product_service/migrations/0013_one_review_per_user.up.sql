@@ -0,0 +1,41 @@
+-- Ulasan yang tidak lolos aturan baru dipindah ke arsip, bukan dihapus, supaya bisa diperiksa atau dikembalikan
+CREATE TABLE product_reviews_archive (
+    id UUID PRIMARY KEY,
+    product_id UUID NOT NULL,
+    user_id UUID,
+    comment TEXT,
+    rating INTEGER,
+    created_at BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL,
+    helpful_count INTEGER NOT NULL,
+    archive_reason VARCHAR(32) NOT NULL CHECK (archive_reason IN ('duplicate', 'rating_out_of_range')),
+    archived_at BIGINT NOT NULL
+);
+
+-- Hanya ulasan terbaru tiap user yang dipertahankan
+WITH archived AS (
+    DELETE FROM product_reviews older USING product_reviews newer
+    WHERE older.product_id = newer.product_id AND older.user_id = newer.user_id
+        AND (older.created_at, older.id) < (newer.created_at, newer.id)
+    RETURNING older.*
+)
+INSERT INTO product_reviews_archive (id, product_id, user_id, comment, rating, created_at, updated_at, helpful_count, archive_reason, archived_at)
+SELECT id, product_id, user_id, comment, rating, created_at, updated_at, helpful_count, 'duplicate', (extract(epoch from now()) * 1000)::bigint
+FROM archived;
+
+-- Rating aslinya disimpan di arsip sebelum dikosongkan
+INSERT INTO product_reviews_archive (id, product_id, user_id, comment, rating, created_at, updated_at, helpful_count, archive_reason, archived_at)
+SELECT id, product_id, user_id, comment, rating, created_at, updated_at, helpful_count, 'rating_out_of_range', (extract(epoch from now()) * 1000)::bigint
+FROM product_reviews WHERE rating NOT BETWEEN 1 AND 5;
+UPDATE product_reviews SET rating = NULL WHERE rating NOT BETWEEN 1 AND 5;
+ALTER TABLE product_reviews ADD CONSTRAINT product_reviews_rating_range CHECK (rating BETWEEN 1 AND 5);
+ALTER TABLE product_reviews ADD CONSTRAINT product_reviews_product_user_key UNIQUE (product_id, user_id);
+
+DELETE FROM product_ratings;
+INSERT INTO product_ratings (product_id, review_count, rating_sum, rating_1, rating_2, rating_3, rating_4, rating_5, updated_at)
+SELECT product_id, COUNT(*),
+    COALESCE(SUM(rating), 0),
+    COUNT(*) FILTER (WHERE rating = 1), COUNT(*) FILTER (WHERE rating = 2), COUNT(*) FILTER (WHERE rating = 3),
+    COUNT(*) FILTER (WHERE rating = 4), COUNT(*) FILTER (WHERE rating = 5),
+    (extract(epoch from now()) * 1000)::bigint
+FROM product_reviews GROUP BY product_id;
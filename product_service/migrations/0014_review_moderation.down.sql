@@ -0,0 +1,6 @@
+DROP TABLE review_reports;
+DROP INDEX idx_product_reviews_moderation;
+ALTER TABLE product_reviews DROP COLUMN moderated_at;
+ALTER TABLE product_reviews DROP COLUMN moderated_by;
+ALTER TABLE product_reviews DROP COLUMN moderation_reason;
+ALTER TABLE product_reviews DROP COLUMN status;
@@ -0,0 +1,17 @@
+ALTER TABLE product_reviews ADD COLUMN status VARCHAR(16) NOT NULL DEFAULT 'pending'
+    CONSTRAINT product_reviews_status_check CHECK (status IN ('pending', 'approved', 'rejected', 'flagged'));
+ALTER TABLE product_reviews ADD COLUMN moderation_reason TEXT;
+ALTER TABLE product_reviews ADD COLUMN moderated_by UUID;
+ALTER TABLE product_reviews ADD COLUMN moderated_at BIGINT;
+-- Ulasan lama sudah tampil dan sudah dihitung di product_ratings
+UPDATE product_reviews SET status = 'approved';
+CREATE INDEX idx_product_reviews_moderation ON product_reviews (created_at, id) WHERE status IN ('pending', 'flagged');
+
+CREATE TABLE review_reports (
+    id UUID PRIMARY KEY,
+    review_id UUID NOT NULL REFERENCES product_reviews (id) ON DELETE CASCADE,
+    user_id UUID NOT NULL,
+    reason TEXT NOT NULL,
+    created_at BIGINT NOT NULL,
+    CONSTRAINT review_reports_review_user_key UNIQUE (review_id, user_id)
+);
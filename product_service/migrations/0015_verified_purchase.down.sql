@@ -0,0 +1,2 @@
+ALTER TABLE categories DROP COLUMN require_verified_purchase;
+ALTER TABLE product_reviews DROP COLUMN verified_purchase;
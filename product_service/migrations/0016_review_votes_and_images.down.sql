@@ -0,0 +1,3 @@
+DROP TABLE review_images;
+DROP TABLE review_votes;
+ALTER TABLE product_reviews DROP COLUMN unhelpful_count;
@@ -0,0 +1,24 @@
+ALTER TABLE product_reviews ADD COLUMN unhelpful_count INTEGER NOT NULL DEFAULT 0;
+
+CREATE TABLE review_votes (
+    review_id UUID NOT NULL REFERENCES product_reviews (id) ON DELETE CASCADE,
+    user_id UUID NOT NULL,
+    helpful BOOLEAN NOT NULL,
+    created_at BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL,
+    PRIMARY KEY (review_id, user_id)
+);
+
+CREATE TABLE review_images (
+    id UUID PRIMARY KEY,
+    review_id UUID NOT NULL REFERENCES product_reviews (id) ON DELETE CASCADE,
+    storage_key VARCHAR(512) NOT NULL,
+    url VARCHAR(1024) NOT NULL,
+    thumbnails JSONB NOT NULL,
+    content_type VARCHAR(100) NOT NULL,
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    created_at BIGINT NOT NULL
+);
+CREATE INDEX idx_review_images_review_id ON review_images (review_id, position);
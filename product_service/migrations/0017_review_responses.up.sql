@@ -0,0 +1,7 @@
+CREATE TABLE review_responses (
+    review_id UUID PRIMARY KEY REFERENCES product_reviews (id) ON DELETE CASCADE,
+    responder_id UUID NOT NULL,
+    body TEXT NOT NULL,
+    created_at BIGINT NOT NULL,
+    updated_at BIGINT NOT NULL
+);
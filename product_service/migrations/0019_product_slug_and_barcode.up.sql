@@ -0,0 +1,37 @@
+ALTER TABLE products ADD COLUMN slug VARCHAR(120), ADD COLUMN barcode VARCHAR(14) UNIQUE;
+
+-- Slug produk lama dibuat dari nama. Produk pertama untuk tiap slug dasar memakai slug itu apa adanya, sisanya
+-- diberi akhiran -2, -3, dst sesuai urutan dibuat dengan melewati slug yang sudah dipakai, karena "Foo 2" juga
+-- menghasilkan foo-2
+CREATE TEMPORARY TABLE product_slug_backfill AS
+SELECT id, created_at, COALESCE(NULLIF(trim(both '-' from left(regexp_replace(lower(name), '[^a-z0-9]+', '-', 'g'), 100)), ''), 'product') AS slug
+FROM products;
+
+WITH numbered AS (
+    SELECT id, slug, row_number() OVER (PARTITION BY slug ORDER BY created_at, id) AS n FROM product_slug_backfill
+)
+UPDATE products SET slug = numbered.slug FROM numbered WHERE products.id = numbered.id AND numbered.n = 1;
+
+DO $$
+DECLARE
+    pending RECORD;
+    candidate TEXT;
+    suffix INTEGER;
+BEGIN
+    FOR pending IN
+        SELECT backfill.id, backfill.slug FROM product_slug_backfill backfill JOIN products ON products.id = backfill.id
+        WHERE products.slug IS NULL ORDER BY backfill.slug, backfill.created_at, backfill.id
+    LOOP
+        suffix := 2;
+        candidate := pending.slug || '-' || suffix;
+        WHILE EXISTS (SELECT 1 FROM products WHERE slug = candidate) LOOP
+            suffix := suffix + 1;
+            candidate := pending.slug || '-' || suffix;
+        END LOOP;
+        UPDATE products SET slug = candidate WHERE id = pending.id;
+    END LOOP;
+END;
+$$;
+DROP TABLE product_slug_backfill;
+
+ALTER TABLE products ALTER COLUMN slug SET NOT NULL, ADD CONSTRAINT products_slug_key UNIQUE (slug);
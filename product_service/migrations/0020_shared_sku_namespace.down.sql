@@ -0,0 +1,3 @@
+DROP TRIGGER product_variants_sku_namespace ON product_variants;
+DROP TRIGGER products_sku_namespace ON products;
+DROP FUNCTION check_sku_namespace();
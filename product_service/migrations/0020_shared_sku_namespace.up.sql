@@ -0,0 +1,36 @@
+-- SKU produk dan SKU varian dipakai bersama saat scan, jadi tidak boleh ada yang sama di kedua tabel
+DO $$
+DECLARE
+    duplicate TEXT;
+BEGIN
+    SELECT string_agg(products.sku, ', ') INTO duplicate
+    FROM products JOIN product_variants ON product_variants.sku = products.sku;
+    IF duplicate IS NOT NULL THEN
+        RAISE EXCEPTION 'sku used by both a product and a variant: %; rename one of them and run the migration again', duplicate;
+    END IF;
+END;
+$$;
+
+CREATE FUNCTION check_sku_namespace() RETURNS trigger AS $$
+BEGIN
+    IF NEW.sku IS NULL THEN
+        RETURN NEW;
+    END IF;
+    -- Lock per SKU supaya insert bersamaan ke dua tabel yang berbeda tidak lolos dua-duanya
+    PERFORM pg_advisory_xact_lock(hashtext('sku:' || NEW.sku));
+    IF (TG_TABLE_NAME = 'products' AND EXISTS (SELECT 1 FROM product_variants WHERE sku = NEW.sku))
+        OR (TG_TABLE_NAME = 'product_variants' AND EXISTS (SELECT 1 FROM products WHERE sku = NEW.sku)) THEN
+        RAISE EXCEPTION 'sku % is already in use', NEW.sku
+            USING ERRCODE = 'unique_violation', CONSTRAINT = 'sku_namespace';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER products_sku_namespace
+    BEFORE INSERT OR UPDATE OF sku ON products
+    FOR EACH ROW EXECUTE FUNCTION check_sku_namespace();
+
+CREATE TRIGGER product_variants_sku_namespace
+    BEFORE INSERT OR UPDATE OF sku ON product_variants
+    FOR EACH ROW EXECUTE FUNCTION check_sku_namespace();
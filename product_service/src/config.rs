@@ -0,0 +1,115 @@
+use std::time::Duration;
+use common::config::{ConfigError, ConfigLoader, ServiceConfig};
+
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+    pub product_ttl: Duration,
+    pub category_ttl: Duration,
+    pub lock_ttl: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct OrderServiceConfig {
+    pub url: String,
+    pub timeout_ms: u64,
+}
+
+#[derive(Debug, Clone)]
+pub enum StorageConfig {
+    Local {
+        path: String,
+        public_url: String,
+    },
+    S3 {
+        bucket: String,
+        endpoint: String,
+        region: String,
+        access_key: String,
+        secret_key: String,
+        public_url: String,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub service: ServiceConfig,
+    pub run_migrations: bool,
+    pub cache: CacheConfig,
+    pub order_service: Option<OrderServiceConfig>,
+    pub storage: StorageConfig,
+    pub thumbnail_sizes: Vec<u32>,
+    pub event_channel_capacity: usize,
+}
+
+impl Config {
+    pub fn load() -> Result<Config, ConfigError> {
+        let mut loader = ConfigLoader::load();
+        let service = ServiceConfig::from_loader(&mut loader);
+
+        let cache = CacheConfig {
+            product_ttl: loader.secs("CACHE_PRODUCT_TTL_SECS", 60),
+            category_ttl: loader.secs("CACHE_CATEGORY_TTL_SECS", 300),
+            lock_ttl: loader.millis("CACHE_LOCK_TTL_MS", 2000),
+        };
+
+        let order_service_timeout = loader.or("ORDER_SERVICE_TIMEOUT_MS", 3000);
+        let order_service = loader.string("ORDER_SERVICE_URL")
+            .map(|url| OrderServiceConfig { url, timeout_ms: order_service_timeout });
+        if order_service.as_ref().is_some_and(|order_service| !is_http_url(&order_service.url)) {
+            loader.error("ORDER_SERVICE_URL must start with http:// or https://");
+        }
+
+        let storage = match loader.string("STORAGE_BACKEND").as_deref() {
+            None | Some("local") => StorageConfig::Local {
+                path: loader.string("STORAGE_LOCAL_PATH").unwrap_or("./media".to_string()),
+                public_url: loader.string("STORAGE_PUBLIC_URL").unwrap_or("/media".to_string()),
+            },
+            Some("s3") => StorageConfig::S3 {
+                bucket: loader.required("S3_BUCKET").unwrap_or_default(),
+                endpoint: loader.string("S3_ENDPOINT").unwrap_or_default(),
+                region: loader.string("S3_REGION").unwrap_or("us-east-1".to_string()),
+                access_key: loader.string("S3_ACCESS_KEY").unwrap_or_default(),
+                secret_key: loader.string("S3_SECRET_KEY").unwrap_or_default(),
+                public_url: loader.string("S3_PUBLIC_URL").unwrap_or_default(),
+            },
+            Some(backend) => {
+                loader.error(format!("STORAGE_BACKEND has invalid value {:?}: expected local or s3", backend));
+                StorageConfig::Local { path: String::new(), public_url: String::new() }
+            }
+        };
+
+        if let StorageConfig::S3 { endpoint, public_url, .. } = &storage {
+            if !endpoint.is_empty() && !is_http_url(endpoint) {
+                loader.error("S3_ENDPOINT must start with http:// or https://");
+            }
+            // Tanpa endpoint (AWS) URL publik tidak bisa diturunkan dari endpoint
+            if endpoint.is_empty() && public_url.is_empty() {
+                loader.error("S3_PUBLIC_URL is required when S3_ENDPOINT is not set");
+            }
+        }
+
+        let thumbnail_sizes = loader.list("THUMBNAIL_SIZES", vec![150, 300, 600]);
+        if thumbnail_sizes.contains(&0) {
+            loader.error("THUMBNAIL_SIZES must not contain 0");
+        }
+        let event_channel_capacity = loader.or("EVENT_CHANNEL_CAPACITY", 1024);
+        if event_channel_capacity == 0 {
+            loader.error("EVENT_CHANNEL_CAPACITY must be greater than 0");
+        }
+
+        let config = Config {
+            service,
+            run_migrations: loader.or("RUN_MIGRATIONS", true),
+            cache,
+            order_service,
+            storage,
+            thumbnail_sizes,
+            event_channel_capacity,
+        };
+        loader.finish(config)
+    }
+}
+
+fn is_http_url(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://")
+}
\ No newline at end of file
@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+use sqlx::migrate::{Migrate, MigrateError, Migrator};
+use sqlx::{Pool, Postgres};
+
+/// Migration di folder `migrations` ikut dikompilasi ke dalam binary.
+pub static MIGRATOR: Migrator = sqlx::migrate!();
+
+pub async fn run(pool: &Pool<Postgres>) -> Result<(), MigrateError> {
+    MIGRATOR.run(pool).await
+}
+
+/// Menangani `product_service migrate <up|down [steps]|status>`.
+pub async fn command(args: &[String], pool: &Pool<Postgres>) -> Result<(), String> {
+    match args.first().map(String::as_str) {
+        Some("up") => run(pool).await.map_err(|error| error.to_string())?,
+        Some("down") => {
+            let steps = match args.get(1) {
+                Some(steps) => steps.parse().ok().filter(|steps| *steps > 0)
+                    .ok_or_else(|| format!("invalid number of steps: {}", steps))?,
+                None => 1,
+            };
+            down(steps, pool).await.map_err(|error| error.to_string())?
+        }
+        Some("status") => {}
+        _ => return Err("usage: product_service migrate <up|down [steps]|status>".to_string()),
+    }
+    status(pool).await.map_err(|error| error.to_string())
+}
+
+async fn down(steps: usize, pool: &Pool<Postgres>) -> Result<(), MigrateError> {
+    let mut conn = pool.acquire().await?;
+    conn.ensure_migrations_table().await?;
+    let mut applied: Vec<i64> = conn.list_applied_migrations().await?
+        .into_iter()
+        .map(|migration| migration.version)
+        .collect();
+    drop(conn);
+
+    // Migrator::undo membatalkan semua versi di atas target, jadi target adalah versi sebelum yang dibatalkan
+    applied.sort_unstable();
+    let target = applied.len().checked_sub(steps + 1).map(|index| applied[index]).unwrap_or(0);
+    MIGRATOR.undo(pool, target).await
+}
+
+async fn status(pool: &Pool<Postgres>) -> Result<(), MigrateError> {
+    let mut conn = pool.acquire().await?;
+    conn.ensure_migrations_table().await?;
+    let applied: HashMap<i64, Vec<u8>> = conn.list_applied_migrations().await?
+        .into_iter()
+        .map(|migration| (migration.version, migration.checksum.into_owned()))
+        .collect();
+
+    for migration in MIGRATOR.iter().filter(|migration| !migration.migration_type.is_down_migration()) {
+        let state = match applied.get(&migration.version) {
+            Some(checksum) if *checksum == *migration.checksum => "applied",
+            Some(_) => "applied (modified)",
+            None => "pending",
+        };
+        println!("{:>4}  {:<32} {}", migration.version, migration.description, state);
+    }
+    if let Some(version) = conn.dirty_version().await? {
+        println!("migration {} is partially applied and needs manual cleanup", version);
+    }
+    Ok(())
+}
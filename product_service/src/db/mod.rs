@@ -1 +1,2 @@
-pub mod pool;
\ No newline at end of file
+pub mod pool;
+pub mod migrations;
\ No newline at end of file
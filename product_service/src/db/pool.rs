@@ -1,8 +1,14 @@
-use sqlx::{PgPool, Pool, Postgres};
-
-pub async fn create_pool(database_url: &str)
-                         -> Pool<Postgres> {
-
-   PgPool::connect(database_url).await.unwrap()
+use std::time::Duration;
+use common::config::DatabaseConfig;
+use sqlx::{Error, Pool, Postgres};
+use sqlx::postgres::PgPoolOptions;
 
+pub async fn create_pool(config: &DatabaseConfig) -> Result<Pool<Postgres>, Error> {
+    PgPoolOptions::new()
+        .max_connections(config.max_connections)
+        .min_connections(config.min_connections)
+        .acquire_timeout(config.acquire_timeout)
+        .idle_timeout(Duration::from_secs(600))
+        .connect(&config.url)
+        .await
 }
\ No newline at end of file
@@ -1,12 +1,26 @@
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
-#[derive(Debug, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Category {
     pub id: Uuid,
     pub name: String,
+    pub parent_id: Option<Uuid>,
+    pub version: i32,
+    pub created_at: i64,  // Epoch time
+    pub updated_at: i64,  // Epoch time
+    pub deleted_at: Option<i64>,  // Epoch time
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CategoryNode {
+    pub id: Uuid,
+    pub name: String,
+    pub parent_id: Option<Uuid>,
+    pub children: Vec<CategoryNode>,
+    pub version: i32,
     pub created_at: i64,  // Epoch time
     pub updated_at: i64,  // Epoch time
 }
@@ -14,9 +28,55 @@ pub struct Category {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateCategory {
     pub name: String,
+    pub parent_id: Option<Uuid>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UpdateCategory {
     pub name: String,
-}
\ No newline at end of file
+    // Tidak dikirim = parent tetap, null = jadikan kategori root
+    #[serde(default, deserialize_with = "double_option")]
+    pub parent_id: Option<Option<Uuid>>,
+    pub version: Option<i32>,
+}
+
+fn double_option<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CategoryQuery {
+    pub include_deleted: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct CategoryReviewPolicy {
+    pub category_id: Uuid,
+    pub require_verified_purchase: bool,
+    pub inherited_require_verified_purchase: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateCategoryReviewPolicy {
+    pub require_verified_purchase: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_category_distinguishes_missing_and_null_parent() {
+        let parent_id = Uuid::new_v4();
+        let missing: UpdateCategory = serde_json::from_str(r#"{"name": "Minuman"}"#).unwrap();
+        let detached: UpdateCategory = serde_json::from_str(r#"{"name": "Minuman", "parent_id": null}"#).unwrap();
+        let moved: UpdateCategory = serde_json::from_str(&format!(r#"{{"name": "Minuman", "parent_id": "{}"}}"#, parent_id)).unwrap();
+        assert_eq!(missing.parent_id, None);
+        assert_eq!(detached.parent_id, Some(None));
+        assert_eq!(moved.parent_id, Some(Some(parent_id)));
+    }
+}
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProductEvent {
+    LowStock(LowStockEvent),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LowStockEvent {
+    pub product_id: Uuid,
+    pub product_name: String,
+    pub stock: i32,
+    pub reorder_threshold: i32,
+    pub occurred_at: i64,  // Epoch time
+}
\ No newline at end of file
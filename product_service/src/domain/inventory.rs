@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+pub enum ReservationStatus {
+    Active,
+    Confirmed,
+    Released,
+    Expired,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct InventoryReservation {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub quantity: i32,
+    pub reference: String,
+    pub status: ReservationStatus,
+    pub expires_at: i64,  // Epoch time
+    pub created_at: i64,  // Epoch time
+    pub updated_at: i64,  // Epoch time
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateReservation {
+    pub product_id: Uuid,
+    pub quantity: i32,
+    pub reference: String,
+    pub ttl_seconds: Option<i64>,
+}
\ No newline at end of file
@@ -1,2 +1,9 @@
 pub mod category;
-pub mod product;
\ No newline at end of file
+pub mod event;
+pub mod inventory;
+pub mod product;
+pub mod product_image;
+pub mod product_import;
+pub mod product_price;
+pub mod product_variant;
+pub mod stock_movement;
\ No newline at end of file
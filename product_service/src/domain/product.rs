@@ -2,34 +2,164 @@ use bigdecimal::BigDecimal;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
+use crate::domain::product_image::{ProductImage, ReviewImage};
+use crate::domain::product_variant::ProductVariant;
 
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct Product {
     pub id: Uuid,
+    pub sku: Option<String>,
+    pub slug: String,
+    pub barcode: Option<String>,  // GTIN-8/12/13/14
     pub name: String,
     pub description: Option<String>,
     pub price: BigDecimal,
     pub stock: i32,
+    pub reserved_stock: i32,
+    pub available_stock: i32,
+    pub reorder_threshold: i32,
+    #[sqlx(flatten)]
+    pub rating: RatingSummary,
     pub category_name: String,
+    pub primary_image_url: Option<String>,
+    #[sqlx(skip)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub images: Option<Vec<ProductImage>>,
+    #[sqlx(skip)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variants: Option<Vec<ProductVariant>>,
+    pub version: i32,
     pub created_at: i64,  // Epoch time
     pub updated_at: i64,  // Epoch time
+    pub deleted_at: Option<i64>,  // Epoch time
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RatingSummary {
+    pub review_count: i32,
+    pub average_rating: Option<BigDecimal>,
+    pub rating_histogram: Vec<i32>,  // Jumlah ulasan bintang 1 sampai 5
+}
+
+impl Default for RatingSummary {
+    fn default() -> Self {
+        RatingSummary {
+            review_count: 0,
+            average_rating: None,
+            rating_histogram: vec![0; 5],
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateProduct {
+    pub sku: Option<String>,
+    pub barcode: Option<String>,
     pub name: String,
     pub description: Option<String>,
     pub price: BigDecimal,
     pub stock: i32,
+    pub reorder_threshold: Option<i32>,
     pub category_id: Uuid
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UpdateProduct {
+    pub sku: Option<String>,
+    pub barcode: Option<String>,
     pub name: Option<String>,
     pub description: Option<String>,
     pub price: Option<BigDecimal>,
-    pub stock: Option<i32>,
+    pub reorder_threshold: Option<i32>,
+    pub version: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProductSort {
+    Price,
+    #[default]
+    CreatedAt,
+    Name,
+    Stock,
+    Rating,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ProductQuery {
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+    pub sort: Option<ProductSort>,
+    pub order: Option<SortOrder>,
+    pub category_id: Option<Uuid>,
+    pub include_descendants: Option<bool>,
+    pub min_price: Option<BigDecimal>,
+    pub max_price: Option<BigDecimal>,
+    pub in_stock: Option<bool>,
+    pub min_rating: Option<BigDecimal>,
+    pub include_deleted: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProductCursor {
+    pub sort: ProductSort,
+    pub order: SortOrder,
+    pub value: String,
+    pub id: Uuid,
+    pub backward: bool,
+}
+
+#[derive(Debug)]
+pub struct ProductPage {
+    pub products: Vec<Product>,
+    pub next_cursor: Option<String>,
+    pub prev_cursor: Option<String>,
+    pub total: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProductSearchQuery {
+    pub q: String,
+    pub limit: Option<i64>,
+    pub category_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct ProductSearchHit {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub price: BigDecimal,
+    pub stock: i32,
+    pub category_name: String,
+    #[sqlx(flatten)]
+    pub rating: RatingSummary,
+    pub rank: f32,
+    pub highlight: String,
+    pub created_at: i64,  // Epoch time
+    pub updated_at: i64,  // Epoch time
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct CategoryFacet {
+    pub category_id: Uuid,
+    pub category_name: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProductSearchResult {
+    pub hits: Vec<ProductSearchHit>,
+    pub facets: Vec<CategoryFacet>,
+    pub total: i64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -40,16 +170,25 @@ pub struct ProductWithReviews {
     pub price: BigDecimal,
     pub stock: i32,
     pub category_name: String,
+    pub rating: RatingSummary,
     pub reviews: Option<Vec<Review>>,
+    pub version: i32,
     pub created_at: i64,  // Epoch time
     pub updated_at: i64,  // Epoch time
+    pub deleted_at: Option<i64>,  // Epoch time
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Review {
+    pub id: Option<Uuid>,
     pub user_id: Option<Uuid>,
     pub comment: Option<String>,
     pub rating: Option<i32>,
+    pub helpful_count: i32,
+    pub unhelpful_count: i32,
+    pub images: Vec<ReviewImage>,
+    pub response: Option<ReviewResponse>,
+    pub verified_purchase: bool,
     pub created_at: Option<i64>
 }
 
@@ -60,13 +199,120 @@ pub struct ProductReview {
     pub user_id: Option<Uuid>,
     pub comment: Option<String>,
     pub rating: Option<i32>,
+    pub helpful_count: i32,
+    pub unhelpful_count: i32,
+    #[sqlx(skip)]
+    pub images: Vec<ReviewImage>,
+    #[sqlx(skip)]
+    pub response: Option<ReviewResponse>,
+    pub verified_purchase: bool,
+    pub status: ReviewStatus,
+    pub moderation_reason: Option<String>,
     pub created_at: Option<i64>,  // Epoch time
     pub updated_at: Option<i64>,  // Epoch time
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+pub enum ReviewStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Flagged,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateProductReview {
-    pub user_id: Uuid,
     pub comment: Option<String>,
     pub rating: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ReviewResponse {
+    pub review_id: Uuid,
+    pub responder_id: Uuid,
+    pub body: String,
+    pub created_at: i64,  // Epoch time
+    pub updated_at: i64,  // Epoch time
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SaveReviewResponse {
+    pub body: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VoteProductReview {
+    pub helpful: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateProductReview {
+    pub comment: Option<String>,
+    pub rating: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewSort {
+    #[default]
+    Newest,
+    Highest,
+    Lowest,
+    MostHelpful,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ReviewQuery {
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+    pub sort: Option<ReviewSort>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReviewCursor {
+    pub sort: ReviewSort,
+    pub value: i64,
+    pub created_at: i64,
+    pub id: Uuid,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ModerationQuery {
+    pub status: Option<ReviewStatus>,
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ModerationCursor {
+    pub created_at: i64,
+    pub id: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RejectProductReview {
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReportProductReview {
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct ReviewReport {
+    pub id: Uuid,
+    pub review_id: Uuid,
+    pub user_id: Uuid,
+    pub reason: String,
+    pub created_at: i64,  // Epoch time
+}
+
+#[derive(Debug)]
+pub struct ReviewPage {
+    pub reviews: Vec<ProductReview>,
+    pub next_cursor: Option<String>,
+    pub total: i64,
 }
\ No newline at end of file
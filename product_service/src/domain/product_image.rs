@@ -0,0 +1,41 @@
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProductImage {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    #[serde(skip)]
+    pub storage_key: String,
+    pub url: String,
+    pub thumbnails: Json<BTreeMap<u32, String>>,  // Ukuran thumbnail (px) -> URL
+    pub content_type: String,
+    pub width: i32,
+    pub height: i32,
+    pub position: i32,
+    pub is_primary: bool,
+    pub created_at: i64,  // Epoch time
+    pub updated_at: i64,  // Epoch time
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReorderProductImages {
+    pub image_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewImage {
+    pub id: Uuid,
+    pub review_id: Uuid,
+    #[serde(skip)]
+    pub storage_key: String,
+    pub url: String,
+    pub thumbnails: Json<BTreeMap<u32, String>>,  // Ukuran thumbnail (px) -> URL
+    pub content_type: String,
+    pub width: i32,
+    pub height: i32,
+    pub position: i32,
+    pub created_at: i64,  // Epoch time
+}
\ No newline at end of file
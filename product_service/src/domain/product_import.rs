@@ -0,0 +1,97 @@
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use common::validation::FieldError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProductFileFormat {
+    Csv,
+    Ndjson,
+}
+
+impl ProductFileFormat {
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let mime = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+        match mime.as_str() {
+            "text/csv" | "application/csv" => Some(ProductFileFormat::Csv),
+            "application/x-ndjson" | "application/ndjson" | "application/jsonl" | "application/x-jsonlines" => Some(ProductFileFormat::Ndjson),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ProductFileFormat::Csv => "text/csv; charset=utf-8",
+            ProductFileFormat::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ProductFileFormat::Csv => "csv",
+            ProductFileFormat::Ndjson => "ndjson",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProductImportQuery {
+    pub format: Option<ProductFileFormat>,
+    pub dry_run: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProductExportQuery {
+    pub format: Option<ProductFileFormat>,
+    pub include_deleted: Option<bool>,
+}
+
+/// Satu baris file import. Semua kolom dibaca sebagai teks supaya kesalahan format bisa dilaporkan per field.
+#[derive(Debug, Default, Deserialize)]
+pub struct ProductImportRow {
+    pub sku: Option<String>,
+    pub barcode: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub price: Option<String>,
+    pub stock: Option<String>,
+    pub reorder_threshold: Option<String>,
+    pub category_id: Option<String>,
+    pub category: Option<String>,  // Nama atau id kategori
+}
+
+/// Hasil export bisa langsung di-import ulang; kolom slug diabaikan saat import karena selalu dibuat dari nama.
+#[derive(Debug, Serialize, FromRow)]
+pub struct ProductExportRow {
+    pub sku: Option<String>,
+    pub slug: String,
+    pub barcode: Option<String>,
+    pub name: String,
+    pub description: Option<String>,
+    pub price: BigDecimal,
+    pub stock: i32,
+    pub reorder_threshold: i32,
+    pub category_id: Uuid,
+    pub category: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportRowError {
+    pub line: u64,
+    pub sku: Option<String>,
+    pub fields: Vec<FieldError>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ProductImportReport {
+    pub dry_run: bool,
+    pub total: usize,
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub errors: Vec<ImportRowError>,
+    #[serde(skip)]
+    pub updated_ids: Vec<Uuid>,  // Dipakai untuk invalidasi cache
+}
\ No newline at end of file
@@ -0,0 +1,35 @@
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+pub enum PriceStatus {
+    Scheduled,
+    Applied,
+    Ended,
+    Cancelled,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct ProductPrice {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub price: BigDecimal,
+    pub previous_price: Option<BigDecimal>,
+    pub effective_from: i64,  // Epoch time
+    pub effective_until: Option<i64>,  // Epoch time
+    pub actor: String,
+    pub status: PriceStatus,
+    pub created_at: i64,  // Epoch time
+    pub updated_at: i64,  // Epoch time
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateScheduledPrice {
+    pub price: BigDecimal,
+    pub effective_from: i64,  // Epoch time
+    pub effective_until: Option<i64>,  // Epoch time
+}
\ No newline at end of file
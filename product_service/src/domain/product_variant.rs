@@ -0,0 +1,51 @@
+use std::collections::BTreeMap;
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProductOptionType {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub name: String,
+    pub values: Vec<String>,
+    pub created_at: i64,  // Epoch time
+    pub updated_at: i64,  // Epoch time
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateProductOptionType {
+    pub name: String,
+    pub values: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProductVariant {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub sku: String,
+    pub price: Option<BigDecimal>,  // Override harga produk, None = pakai harga produk
+    pub stock: i32,
+    pub barcode: Option<String>,
+    pub options: Json<BTreeMap<String, String>>,
+    pub created_at: i64,  // Epoch time
+    pub updated_at: i64,  // Epoch time
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateProductVariant {
+    pub sku: String,
+    pub price: Option<BigDecimal>,
+    pub stock: i32,
+    pub barcode: Option<String>,
+    pub options: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateProductVariant {
+    pub sku: Option<String>,
+    pub price: Option<BigDecimal>,
+    pub stock: Option<i32>,  // Selalu ditolak, stok tidak diubah lewat update varian
+    pub barcode: Option<String>,
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+pub enum StockMovementReason {
+    Restock,
+    Sale,
+    Return,
+    Damage,
+    Correction,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct StockMovement {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub reason: StockMovementReason,
+    pub quantity: i32,
+    pub balance_after: i32,
+    pub actor: String,
+    pub reference: Option<String>,
+    pub created_at: i64,  // Epoch time
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateStockAdjustment {
+    pub reason: StockMovementReason,
+    pub quantity: i32,
+    pub reference: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct StockMovementQuery {
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StockMovementCursor {
+    pub created_at: i64,
+    pub id: Uuid,
+}
+
+#[derive(Debug)]
+pub struct StockMovementPage {
+    pub movements: Vec<StockMovement>,
+    pub next_cursor: Option<String>,
+    pub total: i64,
+}
\ No newline at end of file
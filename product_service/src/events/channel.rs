@@ -0,0 +1,49 @@
+use tokio::sync::{broadcast, watch};
+use tokio::task::JoinHandle;
+use crate::domain::event::ProductEvent;
+use crate::events::EventPublisher;
+
+#[derive(Clone)]
+pub struct ChannelPublisher {
+    sender: broadcast::Sender<ProductEvent>,
+}
+
+impl ChannelPublisher {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        ChannelPublisher { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ProductEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl EventPublisher for ChannelPublisher {
+    async fn publish(&self, event: ProductEvent) {
+        // Tanpa subscriber event dibuang, publisher tidak boleh menggagalkan perubahan stok
+        if self.sender.send(event).is_err() {
+            tracing::debug!("no subscribers for product event");
+        }
+    }
+}
+
+/// Subscriber bawaan yang menulis setiap event ke log sampai notification service berlangganan sendiri.
+pub fn spawn_log_subscriber(publisher: &ChannelPublisher, mut shutdown: watch::Receiver<bool>) -> JoinHandle<()> {
+    let mut receiver = publisher.subscribe();
+    tokio::spawn(async move {
+        loop {
+            // biased supaya event yang sudah antri tetap ditulis sebelum berhenti
+            let received = tokio::select! {
+                biased;
+                received = receiver.recv() => received,
+                _ = shutdown.changed() => break,
+            };
+            match received {
+                Ok(event) => tracing::info!("product event: {}", serde_json::to_string(&event).unwrap_or_default()),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => tracing::warn!("log subscriber skipped {} product events", skipped),
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    })
+}
\ No newline at end of file
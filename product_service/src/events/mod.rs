@@ -0,0 +1,25 @@
+pub mod channel;
+
+use crate::domain::event::ProductEvent;
+use crate::events::channel::ChannelPublisher;
+
+pub trait EventPublisher {
+    async fn publish(&self, event: ProductEvent);
+}
+
+#[derive(Clone)]
+pub enum Publisher {
+    Channel(ChannelPublisher),
+}
+
+impl EventPublisher for Publisher {
+    async fn publish(&self, event: ProductEvent) {
+        match self {
+            Publisher::Channel(publisher) => publisher.publish(event).await,
+        }
+    }
+}
+
+pub fn create_publisher(capacity: usize) -> Publisher {
+    Publisher::Channel(ChannelPublisher::new(capacity))
+}
\ No newline at end of file
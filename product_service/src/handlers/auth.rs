@@ -0,0 +1,77 @@
+use axum::async_trait;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum::response::Response;
+use uuid::Uuid;
+use common::response::ApiError;
+
+// Diisi oleh API gateway setelah token divalidasi authorization service
+pub const USER_ID_HEADER: &str = "x-user-id";
+pub const USER_ROLE_HEADER: &str = "x-user-role";
+const ADMIN_ROLE: &str = "admin";
+const MERCHANT_ROLE: &str = "merchant";
+
+pub struct AuthUser(pub Uuid);
+
+pub struct AdminUser(pub Uuid);
+
+/// Merchant atau admin, misalnya untuk membalas ulasan atas nama toko.
+pub struct MerchantUser(pub Uuid);
+
+fn has_any_role(parts: &Parts, allowed: &[&str]) -> bool {
+    parts.headers.get(USER_ROLE_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|roles| roles.split(',').any(|role| allowed.iter().any(|allowed| role.trim().eq_ignore_ascii_case(allowed))))
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AuthUser
+where
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts.headers.get(USER_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| Uuid::parse_str(value.trim()).ok())
+            .map(AuthUser)
+            .ok_or_else(|| ApiError::Unauthorized.to_response::<()>())
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AdminUser
+where
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let AuthUser(user_id) = AuthUser::from_request_parts(parts, state).await?;
+
+        if has_any_role(parts, &[ADMIN_ROLE]) {
+            Ok(AdminUser(user_id))
+        } else {
+            Err(ApiError::Forbidden.to_response::<()>())
+        }
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for MerchantUser
+where
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let AuthUser(user_id) = AuthUser::from_request_parts(parts, state).await?;
+
+        if has_any_role(parts, &[MERCHANT_ROLE, ADMIN_ROLE]) {
+            Ok(MerchantUser(user_id))
+        } else {
+            Err(ApiError::Forbidden.to_response::<()>())
+        }
+    }
+}
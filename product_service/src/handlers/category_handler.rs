@@ -1,30 +1,55 @@
 use std::sync::Arc;
 use axum::{
-    extract::{Extension, Json, Path},
+    extract::{Extension, Json, Path, Query},
     response::IntoResponse,
-    routing::{get,delete},
-    http::StatusCode,
+    routing::{get, delete, post},
+    http::{HeaderMap, StatusCode},
     Router,
 };
 
 use uuid::Uuid;
-use common::response::{BaseApiResponse, ErrorDetails};
+use common::response::{ApiError, BaseApiResponse, ErrorDetails};
 use crate::{services, AppState};
-use crate::domain::category::{Category, CreateCategory, UpdateCategory};
+use sqlx::Error;
+use crate::domain::category::{Category, CategoryNode, CategoryQuery, CategoryReviewPolicy, CreateCategory, UpdateCategory, UpdateCategoryReviewPolicy};
+use crate::handlers::auth::AdminUser;
+use crate::handlers::etag;
+use crate::redis::cache;
+use crate::services::error::ServiceError;
 use crate::services::service::CategoryService;
 
 pub fn routes() -> Router {
     Router::new()
         .route("/", get(get_all).post(create))
+        .route("/tree", get(get_tree))
         .route("/:id", get(get_by_id).put(update_data))
+        .route("/:id/tree", get(get_subtree))
+        .route("/:id/breadcrumb", get(get_breadcrumb))
         .route("/:id", delete(delete_data))
+        .route("/:id/restore", post(restore_data))
+        .route("/:id/review-policy", get(get_review_policy).put(update_review_policy))
 
 }
 
-async fn get_all(Extension(state): Extension<Arc<AppState>>) -> impl IntoResponse {
+async fn invalidate_category_products(state: &AppState, id: Uuid) {
+    match services::category_service::product_ids(id, &state.pg_pool).await {
+        Ok(ids) => state.cache.invalidate_products(&ids).await,
+        Err(error) => tracing::warn!("failed to load products of category {} for cache invalidation: {}", id, error),
+    }
+}
+
+async fn get_all(Extension(state): Extension<Arc<AppState>>, admin: Option<AdminUser>, Query(query): Query<CategoryQuery>) -> impl IntoResponse {
     let pool = &state.pg_pool;
+    let include_deleted = query.include_deleted.unwrap_or(false);
+    // Kategori yang diarsipkan hanya boleh dilihat admin
+    if include_deleted && admin.is_none() {
+        return ApiError::Forbidden.to_response::<Vec<Category>>();
+    }
 
-    match services::category_service::CategoryServiceImpl.fetch_categories(pool).await {
+    let categories = state.cache.get_or_load(&cache::categories_key(include_deleted), state.cache.category_ttl, || {
+        services::category_service::CategoryServiceImpl.fetch_categories(include_deleted, pool)
+    }).await;
+    match categories {
         Ok(categories) => {
             let response = BaseApiResponse::<Vec<Category>, ErrorDetails>::new(
                 "success",
@@ -55,13 +80,14 @@ async fn get_by_id(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uu
 
     match services::category_service::CategoryServiceImpl.fetch_by_id(id, pool).await {
         Ok(category) => {
+            let version = category.version;
             let response = BaseApiResponse::<Category, ErrorDetails>::new(
                 "success",
                 "Category retrieved successfully!",
                 Some(category),
                 None
             );
-            response.with_status_code(StatusCode::OK)
+            etag::with_etag(response.with_status_code(StatusCode::OK), version)
         }
         Err(error) => {
             let error_details = ErrorDetails {
@@ -79,11 +105,99 @@ async fn get_by_id(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uu
     }
 }
 
+async fn get_tree(Extension(state): Extension<Arc<AppState>>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::category_service::CategoryServiceImpl.fetch_tree(pool).await {
+        Ok(tree) => {
+            let response = BaseApiResponse::<Vec<CategoryNode>, ErrorDetails>::new(
+                "success",
+                "Category tree retrieved successfully!",
+                Some(tree),
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<Vec<CategoryNode>, ErrorDetails>::new(
+                "error",
+                "Failed to fetch category tree",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn get_subtree(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uuid>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::category_service::CategoryServiceImpl.fetch_subtree(id, pool).await {
+        Ok(subtree) => {
+            let response = BaseApiResponse::<CategoryNode, ErrorDetails>::new(
+                "success",
+                "Category tree retrieved successfully!",
+                Some(subtree),
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<CategoryNode, ErrorDetails>::new(
+                "error",
+                "Failed to fetch category tree",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+    }
+}
+
+async fn get_breadcrumb(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uuid>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::category_service::CategoryServiceImpl.fetch_breadcrumb(id, pool).await {
+        Ok(breadcrumb) => {
+            let response = BaseApiResponse::<Vec<Category>, ErrorDetails>::new(
+                "success",
+                "Category breadcrumb retrieved successfully!",
+                Some(breadcrumb),
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<Vec<Category>, ErrorDetails>::new(
+                "error",
+                "Failed to fetch category breadcrumb",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+    }
+}
+
 async fn create(Extension(state): Extension<Arc<AppState>>, Json(request): Json<CreateCategory>) -> impl IntoResponse {
     let pool = &state.pg_pool;
 
-    match services::category_service::CategoryServiceImpl.save(request.name, pool).await {
+    match services::category_service::CategoryServiceImpl.save(request.name, request.parent_id, pool).await {
         Ok(category) => {
+            state.cache.invalidate_categories().await;
             let response = BaseApiResponse::<Category, ErrorDetails>::new(
                 "success",
                 "Category created successfully!",
@@ -108,18 +222,53 @@ async fn create(Extension(state): Extension<Arc<AppState>>, Json(request): Json<
     }
 }
 
-async fn update_data(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uuid>, Json(request): Json<UpdateCategory>) -> impl IntoResponse {
+async fn update_data(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uuid>, headers: HeaderMap, Json(request): Json<UpdateCategory>) -> impl IntoResponse {
     let pool = &state.pg_pool;
 
-    match services::category_service::CategoryServiceImpl.update(id, request.name, pool).await {
+    let Some(version) = etag::if_match_version(&headers).or(request.version) else {
+        let error_details = ErrorDetails {
+            code: "PRECONDITION_REQUIRED".to_string(),
+            message: "If-Match header or version is required".to_string(),
+        };
+        let response = BaseApiResponse::<Category, ErrorDetails>::new(
+            "error",
+            "Failed to update category",
+            None,
+            Some(error_details),
+        );
+        return response.with_status_code(StatusCode::PRECONDITION_REQUIRED);
+    };
+
+    match services::category_service::CategoryServiceImpl.update(id, request.name, request.parent_id, version, pool).await {
         Ok(category) => {
+            state.cache.invalidate_categories().await;
+            invalidate_category_products(&state, id).await;
+            let version = category.version;
             let response = BaseApiResponse::<Category, ErrorDetails>::new(
                 "success",
                 "Category updated successfully!",
                 Some(category),
                 None
             );
-            response.with_status_code(StatusCode::OK)
+            etag::with_etag(response.with_status_code(StatusCode::OK), version)
+        }
+        Err(error @ ServiceError::VersionConflict { .. }) => {
+            let current = services::category_service::CategoryServiceImpl.fetch_by_id(id, pool).await.ok();
+            let current_version = current.as_ref().map(|category| category.version);
+            let error_details = ErrorDetails {
+                code: "CONFLICT".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<Category, ErrorDetails>::new(
+                "error",
+                "Category was modified by another request",
+                current,
+                Some(error_details),
+            ).with_status_code(StatusCode::CONFLICT);
+            match current_version {
+                Some(version) => etag::with_etag(response, version),
+                None => response,
+            }
         }
         Err(error) => {
             let error_details = ErrorDetails {
@@ -143,6 +292,8 @@ async fn delete_data(Extension(state): Extension<Arc<AppState>>, Path(id): Path<
 
     match result {
         Ok(()) => {
+            state.cache.invalidate_categories().await;
+            invalidate_category_products(&state, id).await;
             let response = BaseApiResponse::<Category,ErrorDetails>::new(
                 "success",
                 "Category deleted successfully!",
@@ -151,7 +302,7 @@ async fn delete_data(Extension(state): Extension<Arc<AppState>>, Path(id): Path<
             );
             response.with_status_code(StatusCode::OK)
         }
-        Err(error) => {
+        Err(error @ Error::RowNotFound) => {
             let error_details = ErrorDetails {
                 code: "NOT_FOUND".to_string(),
                 message: error.to_string(),
@@ -164,5 +315,148 @@ async fn delete_data(Extension(state): Extension<Arc<AppState>>, Path(id): Path<
             );
             response.with_status_code(StatusCode::NOT_FOUND)
         }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<Vec<Category>, ErrorDetails>::new(
+                "error",
+                "Failed to delete category",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn restore_data(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uuid>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::category_service::CategoryServiceImpl.restore(id, pool).await {
+        Ok(category) => {
+            state.cache.invalidate_categories().await;
+            invalidate_category_products(&state, id).await;
+            let version = category.version;
+            let response = BaseApiResponse::<Category, ErrorDetails>::new(
+                "success",
+                "Category restored successfully!",
+                Some(category),
+                None
+            );
+            etag::with_etag(response.with_status_code(StatusCode::OK), version)
+        }
+        Err(error @ Error::RowNotFound) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<Category, ErrorDetails>::new(
+                "error",
+                "Failed to restore category",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<Category, ErrorDetails>::new(
+                "error",
+                "Failed to restore category",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn get_review_policy(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uuid>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::category_service::CategoryServiceImpl.fetch_review_policy(id, pool).await {
+        Ok(policy) => {
+            let response = BaseApiResponse::<CategoryReviewPolicy, ErrorDetails>::new(
+                "success",
+                "Category review policy retrieved successfully!",
+                Some(policy),
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<CategoryReviewPolicy, ErrorDetails>::new(
+                "error",
+                "Failed to fetch category review policy",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+    }
+}
+
+async fn update_review_policy(Extension(state): Extension<Arc<AppState>>, _admin: AdminUser, Path(id): Path<Uuid>, Json(request): Json<UpdateCategoryReviewPolicy>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    if request.require_verified_purchase && !state.orders.verifies_purchases() {
+        let error_details = ErrorDetails {
+            code: "BAD_REQUEST".to_string(),
+            message: "verified purchase cannot be required while ORDER_SERVICE_URL is not configured".to_string(),
+        };
+        let response = BaseApiResponse::<CategoryReviewPolicy, ErrorDetails>::new(
+            "error",
+            "Failed to update category review policy",
+            None,
+            Some(error_details),
+        );
+        return response.with_status_code(StatusCode::BAD_REQUEST);
+    }
+
+    match services::category_service::CategoryServiceImpl.update_review_policy(id, request, pool).await {
+        Ok(policy) => {
+            let response = BaseApiResponse::<CategoryReviewPolicy, ErrorDetails>::new(
+                "success",
+                "Category review policy updated successfully!",
+                Some(policy),
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error @ Error::RowNotFound) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<CategoryReviewPolicy, ErrorDetails>::new(
+                "error",
+                "Failed to update category review policy",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<CategoryReviewPolicy, ErrorDetails>::new(
+                "error",
+                "Failed to update category review policy",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,16 @@
+use axum::http::header::{ETAG, IF_MATCH};
+use axum::http::{HeaderMap, HeaderValue};
+use axum::response::Response;
+
+pub fn if_match_version(headers: &HeaderMap) -> Option<i32> {
+    let value = headers.get(IF_MATCH)?.to_str().ok()?.trim();
+    let value = value.strip_prefix("W/").unwrap_or(value);
+    value.trim_matches('"').parse().ok()
+}
+
+pub fn with_etag(mut response: Response, version: i32) -> Response {
+    if let Ok(value) = HeaderValue::from_str(&format!("\"{}\"", version)) {
+        response.headers_mut().insert(ETAG, value);
+    }
+    response
+}
\ No newline at end of file
@@ -0,0 +1,205 @@
+use std::sync::Arc;
+use axum::{Extension, Json, Router};
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::{get, post};
+use sqlx::Error;
+use uuid::Uuid;
+use common::response::{BaseApiResponse, ErrorDetails};
+use crate::{services, AppState};
+use crate::domain::inventory::{CreateReservation, InventoryReservation};
+use crate::services::error::ServiceError;
+use crate::services::service::InventoryService;
+
+pub fn routes() -> Router {
+    Router::new()
+        .route("/reservations", post(create_reservation))
+        .route("/reservations/:id", get(get_reservation))
+        .route("/reservations/:id/confirm", post(confirm_reservation))
+        .route("/reservations/:id/release", post(release_reservation))
+}
+
+async fn create_reservation(Extension(state): Extension<Arc<AppState>>, Json(request): Json<CreateReservation>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::inventory_service::InventoryServiceImpl.reserve(request, pool).await {
+        Ok(reservation) => {
+            state.cache.invalidate_product(reservation.product_id).await;
+            let response = BaseApiResponse::<InventoryReservation, ErrorDetails>::new(
+                "success",
+                "Stock reserved successfully!",
+                Some(reservation),
+                None
+            );
+            response.with_status_code(StatusCode::CREATED)
+        }
+        Err(error @ ServiceError::InsufficientStock { .. }) => {
+            let error_details = ErrorDetails {
+                code: "CONFLICT".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<InventoryReservation, ErrorDetails>::new(
+                "error",
+                "Failed to reserve stock",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::CONFLICT)
+        }
+        Err(error @ ServiceError::Database(Error::RowNotFound)) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<InventoryReservation, ErrorDetails>::new(
+                "error",
+                "Failed to reserve stock",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<InventoryReservation, ErrorDetails>::new(
+                "error",
+                "Failed to reserve stock",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn get_reservation(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uuid>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::inventory_service::InventoryServiceImpl.fetch_reservation(id, pool).await {
+        Ok(reservation) => {
+            let response = BaseApiResponse::<InventoryReservation, ErrorDetails>::new(
+                "success",
+                "Reservation retrieved successfully!",
+                Some(reservation),
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<InventoryReservation, ErrorDetails>::new(
+                "error",
+                "Failed to fetch reservation",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+    }
+}
+
+async fn confirm_reservation(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uuid>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::inventory_service::InventoryServiceImpl.confirm(id, &state.publisher, pool).await {
+        Ok(reservation) => {
+            state.cache.invalidate_product(reservation.product_id).await;
+            let response = BaseApiResponse::<InventoryReservation, ErrorDetails>::new(
+                "success",
+                "Reservation confirmed successfully!",
+                Some(reservation),
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error @ ServiceError::InsufficientStock { .. }) => {
+            let error_details = ErrorDetails {
+                code: "CONFLICT".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<InventoryReservation, ErrorDetails>::new(
+                "error",
+                "Failed to confirm reservation",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::CONFLICT)
+        }
+        Err(error @ ServiceError::Database(Error::RowNotFound)) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<InventoryReservation, ErrorDetails>::new(
+                "error",
+                "Failed to confirm reservation",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<InventoryReservation, ErrorDetails>::new(
+                "error",
+                "Failed to confirm reservation",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn release_reservation(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uuid>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::inventory_service::InventoryServiceImpl.release(id, pool).await {
+        Ok(reservation) => {
+            state.cache.invalidate_product(reservation.product_id).await;
+            let response = BaseApiResponse::<InventoryReservation, ErrorDetails>::new(
+                "success",
+                "Reservation released successfully!",
+                Some(reservation),
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error @ Error::RowNotFound) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<InventoryReservation, ErrorDetails>::new(
+                "error",
+                "Failed to release reservation",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<InventoryReservation, ErrorDetails>::new(
+                "error",
+                "Failed to release reservation",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
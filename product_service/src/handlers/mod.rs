@@ -1,3 +1,10 @@
+pub mod auth;
 pub mod category_handler;
+pub mod etag;
+pub mod inventory_handler;
 pub mod product_handler;
-pub mod product_review_handler;
\ No newline at end of file
+pub mod product_image_handler;
+pub mod product_price_handler;
+pub mod product_review_handler;
+pub mod product_variant_handler;
+pub mod stock_movement_handler;
\ No newline at end of file
@@ -1,20 +1,38 @@
 use std::sync::Arc;
 use axum::{Extension, Json, Router};
-use axum::extract::{Path, Query};
-use axum::http::StatusCode;
+use axum::body::{Body, Bytes};
+use axum::extract::{DefaultBodyLimit, Path, Query};
+use axum::http::{HeaderMap, StatusCode};
+use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
 use axum::response::IntoResponse;
-use axum::routing::{get};
+use axum::routing::{get, post};
 use serde::Deserialize;
+use sqlx::Error;
 use uuid::Uuid;
-use common::response::{BaseApiResponse, ErrorDetails};
+use common::pagination::Pagination;
+use common::response::{ApiError, BaseApiResponse, ErrorDetails};
 use crate::{services, AppState};
-use crate::domain::product::{CreateProduct, CreateProductReview, Product, ProductWithReviews, UpdateProduct};
-use crate::services::service::ProductService;
+use crate::domain::product::{CreateProduct, Product, ProductQuery, ProductSearchQuery, ProductSearchResult, ProductWithReviews, UpdateProduct};
+use crate::domain::product_import::{ProductExportQuery, ProductFileFormat, ProductImportQuery, ProductImportReport};
+use crate::handlers::auth::{AdminUser, MerchantUser};
+use crate::handlers::etag;
+use crate::redis::cache;
+use crate::services::error::ServiceError;
+use crate::services::service::{ProductImportService, ProductService};
+
+const MAX_IMPORT_SIZE: usize = 50 * 1024 * 1024;
 
 pub fn routes() -> Router {
     Router::new()
         .route("/", get(get_all).post(create))
+        .route("/search", get(search))
+        .route("/low-stock", get(get_low_stock))
+        .route("/import", post(import_products).layer(DefaultBodyLimit::max(MAX_IMPORT_SIZE)))
+        .route("/export", get(export_products))
+        .route("/by-slug/:slug", get(get_by_slug))
+        .route("/by-sku/:sku", get(get_by_sku))
         .route("/:id", get(get_by_id).put(update_data).delete(delete_data))
+        .route("/:id/restore", post(restore_data))
 
 }
 
@@ -23,14 +41,81 @@ struct WithReviews {
     with_reviews: Option<bool>, // Parameter query untuk menentukan apakah ulasan disertakan
 }
 
-async fn get_all(Extension(state): Extension<Arc<AppState>>) -> impl IntoResponse {
+async fn get_all(Extension(state): Extension<Arc<AppState>>, admin: Option<AdminUser>, Query(query): Query<ProductQuery>) -> impl IntoResponse {
     let pool = &state.pg_pool;
+    // Produk yang diarsipkan hanya boleh dilihat admin
+    if query.include_deleted == Some(true) && admin.is_none() {
+        return ApiError::Forbidden.to_response::<Vec<Product>>();
+    }
 
-    match services::product_service::ProductServiceImpl.fetch_all(pool).await {
-        Ok(products) => {
+    match services::product_service::ProductServiceImpl.fetch_all(query, pool).await {
+        Ok(page) => {
+            let pagination = Pagination {
+                next_cursor: page.next_cursor,
+                prev_cursor: page.prev_cursor,
+                total: page.total,
+            };
             let response = BaseApiResponse::<Vec<Product>, ErrorDetails>::new(
                 "success",
                 "Products retrieved successfully!",
+                Some(page.products),
+                None
+            ).with_pagination(pagination);
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<Vec<Product>, ErrorDetails>::new(
+                "error",
+                "Failed to fetch products",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn search(Extension(state): Extension<Arc<AppState>>, Query(query): Query<ProductSearchQuery>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_service::ProductServiceImpl.search(query, pool).await {
+        Ok(result) => {
+            let response = BaseApiResponse::<ProductSearchResult, ErrorDetails>::new(
+                "success",
+                "Products retrieved successfully!",
+                Some(result),
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductSearchResult, ErrorDetails>::new(
+                "error",
+                "Failed to search products",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn get_low_stock(Extension(state): Extension<Arc<AppState>>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_service::ProductServiceImpl.fetch_low_stock(pool).await {
+        Ok(products) => {
+            let response = BaseApiResponse::<Vec<Product>, ErrorDetails>::new(
+                "success",
+                "Low-stock products retrieved successfully!",
                 Some(products),
                 None
             );
@@ -43,7 +128,7 @@ async fn get_all(Extension(state): Extension<Arc<AppState>>) -> impl IntoRespons
             };
             let response = BaseApiResponse::<Vec<Product>, ErrorDetails>::new(
                 "error",
-                "Failed to fetch products",
+                "Failed to fetch low-stock products",
                 None,
                 Some(error_details),
             );
@@ -57,15 +142,19 @@ async fn get_by_id(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uu
 
     match with_reviews.with_reviews {
         Some(true) => {
-            match services::product_service::ProductServiceImpl.fetch_by_id_with_reviews(id, pool).await {
+            let product = state.cache.get_or_load(&cache::product_with_reviews_key(id), state.cache.product_ttl, || {
+                services::product_service::ProductServiceImpl.fetch_by_id_with_reviews(id, pool)
+            }).await;
+            match product {
                 Ok(product) => {
+                    let version = product.version;
                     let response = BaseApiResponse::<ProductWithReviews, ErrorDetails>::new(
                         "success",
                         "Product retrieved successfully!",
                         Some(product),
                         None
                     );
-                    response.with_status_code(StatusCode::OK)
+                    etag::with_etag(response.with_status_code(StatusCode::OK), version)
                 }
                 Err(error) => {
                     let error_details = ErrorDetails {
@@ -83,15 +172,19 @@ async fn get_by_id(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uu
             }
         }
         None | Some(false) => {
-            match services::product_service::ProductServiceImpl.fetch_by_id(id, pool).await {
+            let product = state.cache.get_or_load(&cache::product_key(id), state.cache.product_ttl, || {
+                services::product_service::ProductServiceImpl.fetch_by_id(id, pool)
+            }).await;
+            match product {
                 Ok(product) => {
+                    let version = product.version;
                     let response = BaseApiResponse::<Product, ErrorDetails>::new(
                         "success",
                         "Product retrieved successfully!",
                         Some(product),
                         None
                     );
-                    response.with_status_code(StatusCode::OK)
+                    etag::with_etag(response.with_status_code(StatusCode::OK), version)
                 }
                 Err(error) => {
                     let error_details = ErrorDetails {
@@ -112,10 +205,70 @@ async fn get_by_id(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uu
 
 }
 
+async fn get_by_slug(Extension(state): Extension<Arc<AppState>>, Path(slug): Path<String>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_service::ProductServiceImpl.fetch_by_slug(&slug, pool).await {
+        Ok(product) => {
+            let version = product.version;
+            let response = BaseApiResponse::<Product, ErrorDetails>::new(
+                "success",
+                "Product retrieved successfully!",
+                Some(product),
+                None
+            );
+            etag::with_etag(response.with_status_code(StatusCode::OK), version)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<Product, ErrorDetails>::new(
+                "error",
+                "Failed to fetch product",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+    }
+}
+
+async fn get_by_sku(Extension(state): Extension<Arc<AppState>>, Path(sku): Path<String>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_service::ProductServiceImpl.fetch_by_sku(&sku, pool).await {
+        Ok(product) => {
+            let version = product.version;
+            let response = BaseApiResponse::<Product, ErrorDetails>::new(
+                "success",
+                "Product retrieved successfully!",
+                Some(product),
+                None
+            );
+            etag::with_etag(response.with_status_code(StatusCode::OK), version)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<Product, ErrorDetails>::new(
+                "error",
+                "Failed to fetch product",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+    }
+}
+
 async fn create(Extension(state): Extension<Arc<AppState>>, Json(request): Json<CreateProduct>) -> impl IntoResponse {
     let pool = &state.pg_pool;
 
-    match services::product_service::ProductServiceImpl.save(request.name, request.description, request.price, request.stock, request.category_id, pool).await {
+    match services::product_service::ProductServiceImpl.save(request, pool).await {
         Ok(product) => {
             let response = BaseApiResponse::<Product, ErrorDetails>::new(
                 "success",
@@ -125,6 +278,19 @@ async fn create(Extension(state): Extension<Arc<AppState>>, Json(request): Json<
             );
             response.with_status_code(StatusCode::CREATED)
         }
+        Err(Error::Database(error)) if error.is_unique_violation() => {
+            let error_details = ErrorDetails {
+                code: "CONFLICT".to_string(),
+                message: "sku or barcode is already used by another product".to_string(),
+            };
+            let response = BaseApiResponse::<Product, ErrorDetails>::new(
+                "error",
+                "Failed to create product",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::CONFLICT)
+        }
         Err(error) => {
             let error_details = ErrorDetails {
                 code: "BAD_REQUEST".to_string(),
@@ -141,18 +307,65 @@ async fn create(Extension(state): Extension<Arc<AppState>>, Json(request): Json<
     }
 }
 
-async fn update_data(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uuid>, Json(request): Json<UpdateProduct>) -> impl IntoResponse {
+async fn update_data(Extension(state): Extension<Arc<AppState>>, MerchantUser(actor_id): MerchantUser, Path(id): Path<Uuid>, headers: HeaderMap, Json(request): Json<UpdateProduct>) -> impl IntoResponse {
     let pool = &state.pg_pool;
 
-    match services::product_service::ProductServiceImpl.update(id, request.name, request.description, request.price, request.stock, pool).await {
+    let Some(version) = etag::if_match_version(&headers).or(request.version) else {
+        let error_details = ErrorDetails {
+            code: "PRECONDITION_REQUIRED".to_string(),
+            message: "If-Match header or version is required".to_string(),
+        };
+        let response = BaseApiResponse::<Product, ErrorDetails>::new(
+            "error",
+            "Failed to update product",
+            None,
+            Some(error_details),
+        );
+        return response.with_status_code(StatusCode::PRECONDITION_REQUIRED);
+    };
+
+    match services::product_service::ProductServiceImpl.update(id, actor_id, request, version, pool).await {
         Ok(product) => {
+            state.cache.invalidate_product(id).await;
+            let version = product.version;
             let response = BaseApiResponse::<Product, ErrorDetails>::new(
                 "success",
                 "Product updated successfully!",
                 Some(product),
                 None
             );
-            response.with_status_code(StatusCode::OK)
+            etag::with_etag(response.with_status_code(StatusCode::OK), version)
+        }
+        Err(error @ ServiceError::VersionConflict { .. }) => {
+            let current = services::product_service::ProductServiceImpl.fetch_by_id(id, pool).await.ok();
+            let current_version = current.as_ref().map(|product| product.version);
+            let error_details = ErrorDetails {
+                code: "CONFLICT".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<Product, ErrorDetails>::new(
+                "error",
+                "Product was modified by another request",
+                current,
+                Some(error_details),
+            ).with_status_code(StatusCode::CONFLICT);
+            match current_version {
+                Some(version) => etag::with_etag(response, version),
+                None => response,
+            }
+        }
+        Err(ServiceError::Database(Error::Database(error))) if error.is_unique_violation() => {
+            let error_details = ErrorDetails {
+                code: "CONFLICT".to_string(),
+                message: "sku or barcode is already used by another product".to_string(),
+            };
+            let response = BaseApiResponse::<Product, ErrorDetails>::new(
+                "error",
+                "Failed to update product",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::CONFLICT)
         }
         Err(error) => {
             let error_details = ErrorDetails {
@@ -176,6 +389,7 @@ async fn delete_data(Extension(state): Extension<Arc<AppState>>, Path(id): Path<
 
     match result {
         Ok(()) => {
+            state.cache.invalidate_product(id).await;
             let response = BaseApiResponse::<Product,ErrorDetails>::new(
                 "success",
                 "Product deleted successfully!",
@@ -199,3 +413,128 @@ async fn delete_data(Extension(state): Extension<Arc<AppState>>, Path(id): Path<
         }
     }
 }
+
+async fn restore_data(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uuid>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_service::ProductServiceImpl.restore(id, pool).await {
+        Ok(product) => {
+            state.cache.invalidate_product(id).await;
+            let version = product.version;
+            let response = BaseApiResponse::<Product, ErrorDetails>::new(
+                "success",
+                "Product restored successfully!",
+                Some(product),
+                None
+            );
+            etag::with_etag(response.with_status_code(StatusCode::OK), version)
+        }
+        Err(error @ Error::RowNotFound) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<Product, ErrorDetails>::new(
+                "error",
+                "Failed to restore product",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<Product, ErrorDetails>::new(
+                "error",
+                "Failed to restore product",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn import_products(Extension(state): Extension<Arc<AppState>>, AdminUser(actor_id): AdminUser, Query(query): Query<ProductImportQuery>, headers: HeaderMap, body: Bytes) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    let format = query.format.or_else(|| headers.get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(ProductFileFormat::from_content_type));
+    let Some(format) = format else {
+        let error_details = ErrorDetails {
+            code: "BAD_REQUEST".to_string(),
+            message: "format query parameter or a text/csv or application/x-ndjson Content-Type is required".to_string(),
+        };
+        let response = BaseApiResponse::<ProductImportReport, ErrorDetails>::new(
+            "error",
+            "Failed to import products",
+            None,
+            Some(error_details),
+        );
+        return response.with_status_code(StatusCode::BAD_REQUEST);
+    };
+    let dry_run = query.dry_run.unwrap_or(false);
+
+    let result = services::product_import_service::ProductImportServiceImpl.import(body, format, dry_run, &actor_id.to_string(), pool).await;
+    // Batch yang sudah di-commit tetap tersimpan walaupun batch berikutnya gagal
+    if let Ok(report) = &result {
+        state.cache.invalidate_products(&report.updated_ids).await;
+    }
+
+    match result {
+        Ok(report) if report.errors.is_empty() => {
+            let message = if dry_run { "Product import validated successfully!" } else { "Products imported successfully!" };
+            let response = BaseApiResponse::<ProductImportReport, ErrorDetails>::new(
+                "success",
+                message,
+                Some(report),
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Ok(report) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: format!("{} row(s) could not be imported", report.errors.len()),
+            };
+            let response = BaseApiResponse::<ProductImportReport, ErrorDetails>::new(
+                "error",
+                "Failed to import products",
+                Some(report),
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductImportReport, ErrorDetails>::new(
+                "error",
+                "Failed to import products",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn export_products(Extension(state): Extension<Arc<AppState>>, admin: Option<AdminUser>, Query(query): Query<ProductExportQuery>) -> impl IntoResponse {
+    if query.include_deleted == Some(true) && admin.is_none() {
+        return ApiError::Forbidden.to_response::<()>();
+    }
+    let format = query.format.unwrap_or(ProductFileFormat::Csv);
+    let stream = services::product_import_service::ProductImportServiceImpl.export(format, query.include_deleted.unwrap_or(false), state.pg_pool.clone());
+
+    let headers = [
+        (CONTENT_TYPE, format.content_type().to_string()),
+        (CONTENT_DISPOSITION, format!("attachment; filename=\"products.{}\"", format.extension())),
+    ];
+    (headers, Body::from_stream(stream)).into_response()
+}
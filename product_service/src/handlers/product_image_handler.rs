@@ -0,0 +1,194 @@
+use std::sync::Arc;
+use axum::{Extension, Json, Router};
+use axum::extract::{DefaultBodyLimit, Multipart, Path};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::{delete, get, post, put};
+use uuid::Uuid;
+use common::response::{BaseApiResponse, ErrorDetails};
+use crate::{services, AppState};
+use crate::domain::product_image::{ProductImage, ReorderProductImages};
+use crate::handlers::auth::MerchantUser;
+use crate::services::service::ProductImageService;
+
+const MAX_UPLOAD_SIZE: usize = 10 * 1024 * 1024;
+
+pub fn routes() -> Router {
+    Router::new()
+        .route("/:id/images", get(get_images).post(upload_image).layer(DefaultBodyLimit::max(MAX_UPLOAD_SIZE)))
+        .route("/:id/images/order", put(reorder_images))
+        .route("/:id/images/:image_id", delete(delete_image))
+        .route("/:id/images/:image_id/primary", post(set_primary_image))
+}
+
+async fn get_images(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uuid>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_image_service::ProductImageServiceImpl.fetch_images(id, pool).await {
+        Ok(images) => {
+            let response = BaseApiResponse::<Vec<ProductImage>, ErrorDetails>::new(
+                "success",
+                "Product images retrieved successfully!",
+                Some(images),
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<Vec<ProductImage>, ErrorDetails>::new(
+                "error",
+                "Failed to fetch product images",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn upload_image(Extension(state): Extension<Arc<AppState>>, _merchant: MerchantUser, Path(id): Path<Uuid>, mut multipart: Multipart) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    let mut data = None;
+    while let Ok(Some(field)) = multipart.next_field().await {
+        if field.name() == Some("file") {
+            data = field.bytes().await.ok();
+            break;
+        }
+    }
+
+    let Some(data) = data else {
+        let error_details = ErrorDetails {
+            code: "BAD_REQUEST".to_string(),
+            message: "multipart field 'file' is required".to_string(),
+        };
+        let response = BaseApiResponse::<ProductImage, ErrorDetails>::new(
+            "error",
+            "Failed to upload product image",
+            None,
+            Some(error_details),
+        );
+        return response.with_status_code(StatusCode::BAD_REQUEST);
+    };
+
+    match services::product_image_service::ProductImageServiceImpl.save_image(id, data, &state.storage, &state.thumbnail_sizes, pool).await {
+        Ok(image) => {
+            state.cache.invalidate_product(id).await;
+            let response = BaseApiResponse::<ProductImage, ErrorDetails>::new(
+                "success",
+                "Product image uploaded successfully!",
+                Some(image),
+                None
+            );
+            response.with_status_code(StatusCode::CREATED)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductImage, ErrorDetails>::new(
+                "error",
+                "Failed to upload product image",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn reorder_images(Extension(state): Extension<Arc<AppState>>, _merchant: MerchantUser, Path(id): Path<Uuid>, Json(request): Json<ReorderProductImages>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_image_service::ProductImageServiceImpl.reorder(id, request.image_ids, pool).await {
+        Ok(images) => {
+            state.cache.invalidate_product(id).await;
+            let response = BaseApiResponse::<Vec<ProductImage>, ErrorDetails>::new(
+                "success",
+                "Product images reordered successfully!",
+                Some(images),
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<Vec<ProductImage>, ErrorDetails>::new(
+                "error",
+                "Failed to reorder product images",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn set_primary_image(Extension(state): Extension<Arc<AppState>>, _merchant: MerchantUser, Path((id, image_id)): Path<(Uuid, Uuid)>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_image_service::ProductImageServiceImpl.set_primary(id, image_id, pool).await {
+        Ok(images) => {
+            state.cache.invalidate_product(id).await;
+            let response = BaseApiResponse::<Vec<ProductImage>, ErrorDetails>::new(
+                "success",
+                "Primary product image updated successfully!",
+                Some(images),
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<Vec<ProductImage>, ErrorDetails>::new(
+                "error",
+                "Failed to set primary product image",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+    }
+}
+
+async fn delete_image(Extension(state): Extension<Arc<AppState>>, _merchant: MerchantUser, Path((id, image_id)): Path<(Uuid, Uuid)>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+    let result = services::product_image_service::ProductImageServiceImpl.delete_image(id, image_id, &state.storage, pool).await;
+
+    match result {
+        Ok(()) => {
+            state.cache.invalidate_product(id).await;
+            let response = BaseApiResponse::<ProductImage, ErrorDetails>::new(
+                "success",
+                "Product image deleted successfully!",
+                None,
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductImage, ErrorDetails>::new(
+                "error",
+                "Failed to delete product image",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use std::sync::Arc;
+use axum::{Extension, Json, Router};
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::{delete, get, post};
+use sqlx::Error;
+use uuid::Uuid;
+use common::response::{BaseApiResponse, ErrorDetails};
+use crate::{services, AppState};
+use crate::domain::product_price::{CreateScheduledPrice, ProductPrice};
+use crate::handlers::auth::MerchantUser;
+use crate::services::service::ProductPriceService;
+
+pub fn routes() -> Router {
+    Router::new()
+        .route("/:id/price-history", get(get_price_history))
+        .route("/:id/scheduled-prices", post(schedule_price))
+        .route("/:id/scheduled-prices/:price_id", delete(cancel_scheduled_price))
+}
+
+async fn get_price_history(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uuid>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_price_service::ProductPriceServiceImpl.fetch_price_history(id, pool).await {
+        Ok(prices) => {
+            let response = BaseApiResponse::<Vec<ProductPrice>, ErrorDetails>::new(
+                "success",
+                "Price history retrieved successfully!",
+                Some(prices),
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<Vec<ProductPrice>, ErrorDetails>::new(
+                "error",
+                "Failed to fetch price history",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+    }
+}
+
+async fn schedule_price(Extension(state): Extension<Arc<AppState>>, MerchantUser(actor_id): MerchantUser, Path(id): Path<Uuid>, Json(request): Json<CreateScheduledPrice>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_price_service::ProductPriceServiceImpl.schedule_price(id, actor_id, request, pool).await {
+        Ok(price) => {
+            let response = BaseApiResponse::<ProductPrice, ErrorDetails>::new(
+                "success",
+                "Price scheduled successfully!",
+                Some(price),
+                None
+            );
+            response.with_status_code(StatusCode::CREATED)
+        }
+        Err(error @ Error::RowNotFound) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductPrice, ErrorDetails>::new(
+                "error",
+                "Failed to schedule price",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductPrice, ErrorDetails>::new(
+                "error",
+                "Failed to schedule price",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn cancel_scheduled_price(Extension(state): Extension<Arc<AppState>>, Path((id, price_id)): Path<(Uuid, Uuid)>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+    let result = services::product_price_service::ProductPriceServiceImpl.cancel_scheduled_price(id, price_id, pool).await;
+
+    match result {
+        Ok(()) => {
+            let response = BaseApiResponse::<ProductPrice, ErrorDetails>::new(
+                "success",
+                "Scheduled price cancelled successfully!",
+                None,
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductPrice, ErrorDetails>::new(
+                "error",
+                "Failed to cancel scheduled price",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+    }
+}
@@ -1,26 +1,45 @@
 use std::sync::Arc;
 use axum::{Extension, Json, Router};
-use axum::extract::Path;
+use axum::extract::{DefaultBodyLimit, Multipart, Path, Query};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
-use axum::routing::{ post};
+use axum::routing::{delete, get, post, put};
+use sqlx::Error;
 use uuid::Uuid;
-use common::response::{BaseApiResponse, ErrorDetails};
+use common::pagination::Pagination;
+use common::response::{BaseApiResponse, ErrorDetails, ValidationErrorDetails};
 use crate::{services, AppState};
-use crate::domain::product::{CreateProductReview, ProductReview};
+use crate::domain::product::{CreateProductReview, ModerationQuery, ProductReview, RejectProductReview, ReportProductReview, ReviewQuery, ReviewReport, ReviewResponse, SaveReviewResponse, UpdateProductReview, VoteProductReview};
+use crate::domain::product_image::ReviewImage;
+use crate::handlers::auth::{AdminUser, AuthUser, MerchantUser};
+use crate::services::error::ServiceError;
 use crate::services::service::ProductReviewService;
 
+const MAX_UPLOAD_SIZE: usize = 10 * 1024 * 1024;
+
 pub fn routes() -> Router {
     Router::new()
         .route("/:id/review", post(product_add_review))
+        .route("/:id/reviews", get(get_reviews))
+        .route("/:id/reviews/:review_id", put(update_review).delete(delete_review))
+        .route("/:id/reviews/:review_id/report", post(report_review))
+        .route("/:id/reviews/:review_id/vote", put(vote_review).delete(remove_vote))
+        .route("/:id/reviews/:review_id/images", post(upload_review_image).layer(DefaultBodyLimit::max(MAX_UPLOAD_SIZE)))
+        .route("/:id/reviews/:review_id/images/:image_id", delete(delete_review_image))
+        .route("/:id/reviews/:review_id/response", post(create_response).put(update_response).delete(delete_response))
+        .route("/:id/reviews/:review_id/approve", post(approve_review))
+        .route("/:id/reviews/:review_id/reject", post(reject_review))
+        .route("/reviews/moderation", get(get_moderation_queue))
 
 }
 
-async fn product_add_review(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uuid>, Json(request): Json<CreateProductReview>) -> impl IntoResponse {
+async fn product_add_review(Extension(state): Extension<Arc<AppState>>, AuthUser(user_id): AuthUser, Path(id): Path<Uuid>, Json(request): Json<CreateProductReview>) -> impl IntoResponse {
     let pool = &state.pg_pool;
 
-    match services::product_review_service::ProductReviewServiceImpl.save(id, request.user_id, request.comment, request.rating,  pool).await {
+    // Penulis ulasan selalu diambil dari header yang diisi gateway, bukan dari body
+    match services::product_review_service::ProductReviewServiceImpl.save(id, user_id, request, &state.orders, pool).await {
         Ok(product_review) => {
+            state.cache.invalidate_product(id).await;
             let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
                 "success",
                 "Product review created successfully!",
@@ -29,6 +48,72 @@ async fn product_add_review(Extension(state): Extension<Arc<AppState>>, Path(id)
             );
             response.with_status_code(StatusCode::CREATED)
         }
+        Err(ServiceError::Validation(errors)) => {
+            let error_details = ValidationErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: errors.to_string(),
+                fields: errors.fields,
+            };
+            let response = BaseApiResponse::<ProductReview, ValidationErrorDetails>::new(
+                "error",
+                "Failed to create product review",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+        Err(error @ ServiceError::Forbidden(_)) => {
+            let error_details = ErrorDetails {
+                code: "FORBIDDEN".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "error",
+                "Failed to create product review",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::FORBIDDEN)
+        }
+        Err(error @ ServiceError::Unavailable(_)) => {
+            let error_details = ErrorDetails {
+                code: "SERVICE_UNAVAILABLE".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "error",
+                "Failed to create product review",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::SERVICE_UNAVAILABLE)
+        }
+        Err(error @ ServiceError::Conflict(_)) => {
+            let error_details = ErrorDetails {
+                code: "CONFLICT".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "error",
+                "Failed to create product review",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::CONFLICT)
+        }
+        Err(error @ ServiceError::Database(Error::RowNotFound)) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "error",
+                "Failed to create product review",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
         Err(error) => {
             let error_details = ErrorDetails {
                 code: "BAD_REQUEST".to_string(),
@@ -43,4 +128,814 @@ async fn product_add_review(Extension(state): Extension<Arc<AppState>>, Path(id)
             response.with_status_code(StatusCode::BAD_REQUEST)
         }
     }
+}
+
+async fn get_reviews(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uuid>, Query(query): Query<ReviewQuery>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_review_service::ProductReviewServiceImpl.fetch_reviews(id, query, pool).await {
+        Ok(page) => {
+            let pagination = Pagination {
+                next_cursor: page.next_cursor,
+                prev_cursor: None,
+                total: page.total,
+            };
+            let response = BaseApiResponse::<Vec<ProductReview>, ErrorDetails>::new(
+                "success",
+                "Product reviews retrieved successfully!",
+                Some(page.reviews),
+                None
+            ).with_pagination(pagination);
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error @ Error::RowNotFound) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<Vec<ProductReview>, ErrorDetails>::new(
+                "error",
+                "Failed to fetch product reviews",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<Vec<ProductReview>, ErrorDetails>::new(
+                "error",
+                "Failed to fetch product reviews",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn update_review(Extension(state): Extension<Arc<AppState>>, AuthUser(user_id): AuthUser, Path((id, review_id)): Path<(Uuid, Uuid)>, Json(request): Json<UpdateProductReview>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_review_service::ProductReviewServiceImpl.update(id, review_id, user_id, request, pool).await {
+        Ok(product_review) => {
+            state.cache.invalidate_product(id).await;
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "success",
+                "Product review updated successfully!",
+                Some(product_review),
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(ServiceError::Validation(errors)) => {
+            let error_details = ValidationErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: errors.to_string(),
+                fields: errors.fields,
+            };
+            let response = BaseApiResponse::<ProductReview, ValidationErrorDetails>::new(
+                "error",
+                "Failed to update product review",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+        Err(error @ ServiceError::Forbidden(_)) => {
+            let error_details = ErrorDetails {
+                code: "FORBIDDEN".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "error",
+                "Failed to update product review",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::FORBIDDEN)
+        }
+        Err(error @ ServiceError::Database(Error::RowNotFound)) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "error",
+                "Failed to update product review",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "error",
+                "Failed to update product review",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn delete_review(Extension(state): Extension<Arc<AppState>>, AuthUser(user_id): AuthUser, Path((id, review_id)): Path<(Uuid, Uuid)>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+    let result = services::product_review_service::ProductReviewServiceImpl.delete(id, review_id, user_id, &state.storage, pool).await;
+
+    match result {
+        Ok(()) => {
+            state.cache.invalidate_product(id).await;
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "success",
+                "Product review deleted successfully!",
+                None,
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error @ ServiceError::Forbidden(_)) => {
+            let error_details = ErrorDetails {
+                code: "FORBIDDEN".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "error",
+                "Failed to delete product review",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::FORBIDDEN)
+        }
+        Err(error @ ServiceError::Database(Error::RowNotFound)) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "error",
+                "Failed to delete product review",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "error",
+                "Failed to delete product review",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn report_review(Extension(state): Extension<Arc<AppState>>, AuthUser(user_id): AuthUser, Path((id, review_id)): Path<(Uuid, Uuid)>, Json(request): Json<ReportProductReview>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_review_service::ProductReviewServiceImpl.report(id, review_id, user_id, request, pool).await {
+        Ok(report) => {
+            state.cache.invalidate_product(id).await;
+            let response = BaseApiResponse::<ReviewReport, ErrorDetails>::new(
+                "success",
+                "Product review reported successfully!",
+                Some(report),
+                None
+            );
+            response.with_status_code(StatusCode::CREATED)
+        }
+        Err(ServiceError::Validation(errors)) => {
+            let error_details = ValidationErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: errors.to_string(),
+                fields: errors.fields,
+            };
+            let response = BaseApiResponse::<ReviewReport, ValidationErrorDetails>::new(
+                "error",
+                "Failed to report product review",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+        Err(error @ ServiceError::Conflict(_)) => {
+            let error_details = ErrorDetails {
+                code: "CONFLICT".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ReviewReport, ErrorDetails>::new(
+                "error",
+                "Failed to report product review",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::CONFLICT)
+        }
+        Err(error @ ServiceError::Database(Error::RowNotFound)) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ReviewReport, ErrorDetails>::new(
+                "error",
+                "Failed to report product review",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ReviewReport, ErrorDetails>::new(
+                "error",
+                "Failed to report product review",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn get_moderation_queue(Extension(state): Extension<Arc<AppState>>, _admin: AdminUser, Query(query): Query<ModerationQuery>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_review_service::ProductReviewServiceImpl.fetch_moderation_queue(query, pool).await {
+        Ok(page) => {
+            let pagination = Pagination {
+                next_cursor: page.next_cursor,
+                prev_cursor: None,
+                total: page.total,
+            };
+            let response = BaseApiResponse::<Vec<ProductReview>, ErrorDetails>::new(
+                "success",
+                "Review moderation queue retrieved successfully!",
+                Some(page.reviews),
+                None
+            ).with_pagination(pagination);
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<Vec<ProductReview>, ErrorDetails>::new(
+                "error",
+                "Failed to fetch review moderation queue",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn approve_review(Extension(state): Extension<Arc<AppState>>, AdminUser(moderator): AdminUser, Path((id, review_id)): Path<(Uuid, Uuid)>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_review_service::ProductReviewServiceImpl.approve(id, review_id, moderator, pool).await {
+        Ok(product_review) => {
+            state.cache.invalidate_product(id).await;
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "success",
+                "Product review approved successfully!",
+                Some(product_review),
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error @ ServiceError::Database(Error::RowNotFound)) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "error",
+                "Failed to approve product review",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "error",
+                "Failed to approve product review",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn reject_review(Extension(state): Extension<Arc<AppState>>, AdminUser(moderator): AdminUser, Path((id, review_id)): Path<(Uuid, Uuid)>, Json(request): Json<RejectProductReview>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_review_service::ProductReviewServiceImpl.reject(id, review_id, moderator, request, pool).await {
+        Ok(product_review) => {
+            state.cache.invalidate_product(id).await;
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "success",
+                "Product review rejected successfully!",
+                Some(product_review),
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(ServiceError::Validation(errors)) => {
+            let error_details = ValidationErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: errors.to_string(),
+                fields: errors.fields,
+            };
+            let response = BaseApiResponse::<ProductReview, ValidationErrorDetails>::new(
+                "error",
+                "Failed to reject product review",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+        Err(error @ ServiceError::Database(Error::RowNotFound)) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "error",
+                "Failed to reject product review",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "error",
+                "Failed to reject product review",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn vote_review(Extension(state): Extension<Arc<AppState>>, AuthUser(user_id): AuthUser, Path((id, review_id)): Path<(Uuid, Uuid)>, Json(request): Json<VoteProductReview>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_review_service::ProductReviewServiceImpl.vote(id, review_id, user_id, request, pool).await {
+        Ok(product_review) => {
+            state.cache.invalidate_product(id).await;
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "success",
+                "Product review vote recorded successfully!",
+                Some(product_review),
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error @ ServiceError::Forbidden(_)) => {
+            let error_details = ErrorDetails {
+                code: "FORBIDDEN".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "error",
+                "Failed to vote on product review",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::FORBIDDEN)
+        }
+        Err(error @ ServiceError::Database(Error::RowNotFound)) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "error",
+                "Failed to vote on product review",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "error",
+                "Failed to vote on product review",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn remove_vote(Extension(state): Extension<Arc<AppState>>, AuthUser(user_id): AuthUser, Path((id, review_id)): Path<(Uuid, Uuid)>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_review_service::ProductReviewServiceImpl.remove_vote(id, review_id, user_id, pool).await {
+        Ok(product_review) => {
+            state.cache.invalidate_product(id).await;
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "success",
+                "Product review vote removed successfully!",
+                Some(product_review),
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error @ ServiceError::Forbidden(_)) => {
+            let error_details = ErrorDetails {
+                code: "FORBIDDEN".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "error",
+                "Failed to remove product review vote",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::FORBIDDEN)
+        }
+        Err(error @ ServiceError::Database(Error::RowNotFound)) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "error",
+                "Failed to remove product review vote",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductReview, ErrorDetails>::new(
+                "error",
+                "Failed to remove product review vote",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn upload_review_image(Extension(state): Extension<Arc<AppState>>, AuthUser(user_id): AuthUser, Path((id, review_id)): Path<(Uuid, Uuid)>, mut multipart: Multipart) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    let mut data = None;
+    while let Ok(Some(field)) = multipart.next_field().await {
+        if field.name() == Some("file") {
+            data = field.bytes().await.ok();
+            break;
+        }
+    }
+
+    let Some(data) = data else {
+        let error_details = ErrorDetails {
+            code: "BAD_REQUEST".to_string(),
+            message: "multipart field 'file' is required".to_string(),
+        };
+        let response = BaseApiResponse::<ReviewImage, ErrorDetails>::new(
+            "error",
+            "Failed to upload review image",
+            None,
+            Some(error_details),
+        );
+        return response.with_status_code(StatusCode::BAD_REQUEST);
+    };
+
+    match services::product_review_service::ProductReviewServiceImpl.save_image(id, review_id, user_id, data, &state.storage, pool).await {
+        Ok(image) => {
+            state.cache.invalidate_product(id).await;
+            let response = BaseApiResponse::<ReviewImage, ErrorDetails>::new(
+                "success",
+                "Review image uploaded successfully!",
+                Some(image),
+                None
+            );
+            response.with_status_code(StatusCode::CREATED)
+        }
+        Err(ServiceError::Validation(errors)) => {
+            let error_details = ValidationErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: errors.to_string(),
+                fields: errors.fields,
+            };
+            let response = BaseApiResponse::<ReviewImage, ValidationErrorDetails>::new(
+                "error",
+                "Failed to upload review image",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+        Err(error @ ServiceError::Forbidden(_)) => {
+            let error_details = ErrorDetails {
+                code: "FORBIDDEN".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ReviewImage, ErrorDetails>::new(
+                "error",
+                "Failed to upload review image",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::FORBIDDEN)
+        }
+        Err(error @ ServiceError::Database(Error::RowNotFound)) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ReviewImage, ErrorDetails>::new(
+                "error",
+                "Failed to upload review image",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ReviewImage, ErrorDetails>::new(
+                "error",
+                "Failed to upload review image",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn delete_review_image(Extension(state): Extension<Arc<AppState>>, AuthUser(user_id): AuthUser, Path((id, review_id, image_id)): Path<(Uuid, Uuid, Uuid)>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+    let result = services::product_review_service::ProductReviewServiceImpl.delete_image(id, review_id, image_id, user_id, &state.storage, pool).await;
+
+    match result {
+        Ok(()) => {
+            state.cache.invalidate_product(id).await;
+            let response = BaseApiResponse::<ReviewImage, ErrorDetails>::new(
+                "success",
+                "Review image deleted successfully!",
+                None,
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error @ ServiceError::Forbidden(_)) => {
+            let error_details = ErrorDetails {
+                code: "FORBIDDEN".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ReviewImage, ErrorDetails>::new(
+                "error",
+                "Failed to delete review image",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::FORBIDDEN)
+        }
+        Err(error @ ServiceError::Database(Error::RowNotFound)) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ReviewImage, ErrorDetails>::new(
+                "error",
+                "Failed to delete review image",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ReviewImage, ErrorDetails>::new(
+                "error",
+                "Failed to delete review image",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn create_response(Extension(state): Extension<Arc<AppState>>, MerchantUser(responder_id): MerchantUser, Path((id, review_id)): Path<(Uuid, Uuid)>, Json(request): Json<SaveReviewResponse>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_review_service::ProductReviewServiceImpl.save_response(id, review_id, responder_id, request, pool).await {
+        Ok(review_response) => {
+            state.cache.invalidate_product(id).await;
+            let response = BaseApiResponse::<ReviewResponse, ErrorDetails>::new(
+                "success",
+                "Review response created successfully!",
+                Some(review_response),
+                None
+            );
+            response.with_status_code(StatusCode::CREATED)
+        }
+        Err(ServiceError::Validation(errors)) => {
+            let error_details = ValidationErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: errors.to_string(),
+                fields: errors.fields,
+            };
+            let response = BaseApiResponse::<ReviewResponse, ValidationErrorDetails>::new(
+                "error",
+                "Failed to create review response",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+        Err(error @ ServiceError::Conflict(_)) => {
+            let error_details = ErrorDetails {
+                code: "CONFLICT".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ReviewResponse, ErrorDetails>::new(
+                "error",
+                "Failed to create review response",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::CONFLICT)
+        }
+        Err(error @ ServiceError::Database(Error::RowNotFound)) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ReviewResponse, ErrorDetails>::new(
+                "error",
+                "Failed to create review response",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ReviewResponse, ErrorDetails>::new(
+                "error",
+                "Failed to create review response",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn update_response(Extension(state): Extension<Arc<AppState>>, MerchantUser(responder_id): MerchantUser, Path((id, review_id)): Path<(Uuid, Uuid)>, Json(request): Json<SaveReviewResponse>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_review_service::ProductReviewServiceImpl.update_response(id, review_id, responder_id, request, pool).await {
+        Ok(review_response) => {
+            state.cache.invalidate_product(id).await;
+            let response = BaseApiResponse::<ReviewResponse, ErrorDetails>::new(
+                "success",
+                "Review response updated successfully!",
+                Some(review_response),
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(ServiceError::Validation(errors)) => {
+            let error_details = ValidationErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: errors.to_string(),
+                fields: errors.fields,
+            };
+            let response = BaseApiResponse::<ReviewResponse, ValidationErrorDetails>::new(
+                "error",
+                "Failed to update review response",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+        Err(error @ ServiceError::Database(Error::RowNotFound)) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ReviewResponse, ErrorDetails>::new(
+                "error",
+                "Failed to update review response",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ReviewResponse, ErrorDetails>::new(
+                "error",
+                "Failed to update review response",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn delete_response(Extension(state): Extension<Arc<AppState>>, _merchant: MerchantUser, Path((id, review_id)): Path<(Uuid, Uuid)>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+    let result = services::product_review_service::ProductReviewServiceImpl.delete_response(id, review_id, pool).await;
+
+    match result {
+        Ok(()) => {
+            state.cache.invalidate_product(id).await;
+            let response = BaseApiResponse::<ReviewResponse, ErrorDetails>::new(
+                "success",
+                "Review response deleted successfully!",
+                None,
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error @ ServiceError::Database(Error::RowNotFound)) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ReviewResponse, ErrorDetails>::new(
+                "error",
+                "Failed to delete review response",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ReviewResponse, ErrorDetails>::new(
+                "error",
+                "Failed to delete review response",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
 }
\ No newline at end of file
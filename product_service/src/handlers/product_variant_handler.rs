@@ -0,0 +1,256 @@
+use std::sync::Arc;
+use axum::{Extension, Json, Router};
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::{delete, get};
+use uuid::Uuid;
+use common::response::{BaseApiResponse, ErrorDetails};
+use crate::{services, AppState};
+use crate::domain::product_variant::{CreateProductOptionType, CreateProductVariant, ProductOptionType, ProductVariant, UpdateProductVariant};
+use crate::services::service::ProductVariantService;
+
+pub fn routes() -> Router {
+    Router::new()
+        .route("/:id/options", get(get_option_types).post(create_option_type))
+        .route("/:id/options/:option_id", delete(delete_option_type))
+        .route("/:id/variants", get(get_variants).post(create_variant))
+        .route("/:id/variants/:variant_id", get(get_variant).put(update_variant).delete(delete_variant))
+}
+
+async fn get_option_types(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uuid>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_variant_service::ProductVariantServiceImpl.fetch_option_types(id, pool).await {
+        Ok(option_types) => {
+            let response = BaseApiResponse::<Vec<ProductOptionType>, ErrorDetails>::new(
+                "success",
+                "Product option types retrieved successfully!",
+                Some(option_types),
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<Vec<ProductOptionType>, ErrorDetails>::new(
+                "error",
+                "Failed to fetch product option types",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn create_option_type(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uuid>, Json(request): Json<CreateProductOptionType>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_variant_service::ProductVariantServiceImpl.save_option_type(id, request, pool).await {
+        Ok(option_type) => {
+            let response = BaseApiResponse::<ProductOptionType, ErrorDetails>::new(
+                "success",
+                "Product option type created successfully!",
+                Some(option_type),
+                None
+            );
+            response.with_status_code(StatusCode::CREATED)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductOptionType, ErrorDetails>::new(
+                "error",
+                "Failed to create product option type",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn delete_option_type(Extension(state): Extension<Arc<AppState>>, Path((id, option_id)): Path<(Uuid, Uuid)>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+    let result = services::product_variant_service::ProductVariantServiceImpl.delete_option_type(id, option_id, pool).await;
+
+    match result {
+        Ok(()) => {
+            let response = BaseApiResponse::<ProductOptionType, ErrorDetails>::new(
+                "success",
+                "Product option type deleted successfully!",
+                None,
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductOptionType, ErrorDetails>::new(
+                "error",
+                "Failed to delete product option type",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn get_variants(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uuid>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_variant_service::ProductVariantServiceImpl.fetch_variants(id, pool).await {
+        Ok(variants) => {
+            let response = BaseApiResponse::<Vec<ProductVariant>, ErrorDetails>::new(
+                "success",
+                "Product variants retrieved successfully!",
+                Some(variants),
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<Vec<ProductVariant>, ErrorDetails>::new(
+                "error",
+                "Failed to fetch product variants",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn get_variant(Extension(state): Extension<Arc<AppState>>, Path((id, variant_id)): Path<(Uuid, Uuid)>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_variant_service::ProductVariantServiceImpl.fetch_variant(id, variant_id, pool).await {
+        Ok(variant) => {
+            let response = BaseApiResponse::<ProductVariant, ErrorDetails>::new(
+                "success",
+                "Product variant retrieved successfully!",
+                Some(variant),
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductVariant, ErrorDetails>::new(
+                "error",
+                "Failed to fetch product variant",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+    }
+}
+
+async fn create_variant(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uuid>, Json(request): Json<CreateProductVariant>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_variant_service::ProductVariantServiceImpl.save_variant(id, request, pool).await {
+        Ok(variant) => {
+            state.cache.invalidate_product(id).await;
+            let response = BaseApiResponse::<ProductVariant, ErrorDetails>::new(
+                "success",
+                "Product variant created successfully!",
+                Some(variant),
+                None
+            );
+            response.with_status_code(StatusCode::CREATED)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductVariant, ErrorDetails>::new(
+                "error",
+                "Failed to create product variant",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn update_variant(Extension(state): Extension<Arc<AppState>>, Path((id, variant_id)): Path<(Uuid, Uuid)>, Json(request): Json<UpdateProductVariant>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::product_variant_service::ProductVariantServiceImpl.update_variant(id, variant_id, request, pool).await {
+        Ok(variant) => {
+            state.cache.invalidate_product(id).await;
+            let response = BaseApiResponse::<ProductVariant, ErrorDetails>::new(
+                "success",
+                "Product variant updated successfully!",
+                Some(variant),
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductVariant, ErrorDetails>::new(
+                "error",
+                "Failed to update product variant",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn delete_variant(Extension(state): Extension<Arc<AppState>>, Path((id, variant_id)): Path<(Uuid, Uuid)>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+    let result = services::product_variant_service::ProductVariantServiceImpl.delete_variant(id, variant_id, pool).await;
+
+    match result {
+        Ok(()) => {
+            state.cache.invalidate_product(id).await;
+            let response = BaseApiResponse::<ProductVariant, ErrorDetails>::new(
+                "success",
+                "Product variant deleted successfully!",
+                None,
+                None
+            );
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<ProductVariant, ErrorDetails>::new(
+                "error",
+                "Failed to delete product variant",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+    }
+}
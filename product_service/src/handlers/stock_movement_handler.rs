@@ -0,0 +1,124 @@
+use std::sync::Arc;
+use axum::{Extension, Json, Router};
+use axum::extract::{Path, Query};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::{get, post};
+use sqlx::Error;
+use uuid::Uuid;
+use common::pagination::Pagination;
+use common::response::{BaseApiResponse, ErrorDetails};
+use crate::{services, AppState};
+use crate::domain::stock_movement::{CreateStockAdjustment, StockMovement, StockMovementQuery};
+use crate::handlers::auth::MerchantUser;
+use crate::services::error::ServiceError;
+use crate::services::service::StockMovementService;
+
+pub fn routes() -> Router {
+    Router::new()
+        .route("/:id/stock-adjustments", post(create_adjustment))
+        .route("/:id/stock-movements", get(get_movements))
+}
+
+async fn create_adjustment(Extension(state): Extension<Arc<AppState>>, MerchantUser(actor_id): MerchantUser, Path(id): Path<Uuid>, Json(request): Json<CreateStockAdjustment>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::stock_movement_service::StockMovementServiceImpl.adjust(id, actor_id, request, &state.publisher, pool).await {
+        Ok(movement) => {
+            state.cache.invalidate_product(id).await;
+            let response = BaseApiResponse::<StockMovement, ErrorDetails>::new(
+                "success",
+                "Stock adjusted successfully!",
+                Some(movement),
+                None
+            );
+            response.with_status_code(StatusCode::CREATED)
+        }
+        Err(error @ ServiceError::InsufficientStock { .. }) => {
+            let error_details = ErrorDetails {
+                code: "CONFLICT".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<StockMovement, ErrorDetails>::new(
+                "error",
+                "Failed to adjust stock",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::CONFLICT)
+        }
+        Err(error @ ServiceError::Database(Error::RowNotFound)) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<StockMovement, ErrorDetails>::new(
+                "error",
+                "Failed to adjust stock",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<StockMovement, ErrorDetails>::new(
+                "error",
+                "Failed to adjust stock",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn get_movements(Extension(state): Extension<Arc<AppState>>, Path(id): Path<Uuid>, Query(query): Query<StockMovementQuery>) -> impl IntoResponse {
+    let pool = &state.pg_pool;
+
+    match services::stock_movement_service::StockMovementServiceImpl.fetch_movements(id, query, pool).await {
+        Ok(page) => {
+            let pagination = Pagination {
+                next_cursor: page.next_cursor,
+                prev_cursor: None,
+                total: page.total,
+            };
+            let response = BaseApiResponse::<Vec<StockMovement>, ErrorDetails>::new(
+                "success",
+                "Stock movements retrieved successfully!",
+                Some(page.movements),
+                None
+            ).with_pagination(pagination);
+            response.with_status_code(StatusCode::OK)
+        }
+        Err(error @ Error::RowNotFound) => {
+            let error_details = ErrorDetails {
+                code: "NOT_FOUND".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<Vec<StockMovement>, ErrorDetails>::new(
+                "error",
+                "Failed to fetch stock movements",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::NOT_FOUND)
+        }
+        Err(error) => {
+            let error_details = ErrorDetails {
+                code: "BAD_REQUEST".to_string(),
+                message: error.to_string(),
+            };
+            let response = BaseApiResponse::<Vec<StockMovement>, ErrorDetails>::new(
+                "error",
+                "Failed to fetch stock movements",
+                None,
+                Some(error_details),
+            );
+            response.with_status_code(StatusCode::BAD_REQUEST)
+        }
+    }
+}
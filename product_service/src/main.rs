@@ -1,36 +1,200 @@
+use std::future::IntoFuture;
 use std::sync::Arc;
+use std::time::Duration;
 use axum::Extension;
-use dotenvy::dotenv;
+use common::health::Health;
 use sqlx::{Pool, Postgres};
+use tokio::sync::watch;
+use tokio::time::Instant;
+use tower_http::services::ServeDir;
+use crate::config::Config;
+use crate::events::Publisher;
+use crate::orders::Orders;
+use crate::redis::cache::Cache;
+use crate::storage::Storage;
 
+mod config;
 mod handlers;
 mod db;
 mod redis;
 mod services;
 mod routes;
 mod domain;
+mod storage;
+mod events;
+mod orders;
 
 
+const POOL_CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[derive(Clone)]
 pub struct AppState {
     pub pg_pool: Pool<Postgres>,
+    pub storage: Storage,
+    pub thumbnail_sizes: Vec<u32>,
+    pub publisher: Publisher,
+    pub orders: Orders,
+    pub cache: Cache,
 }
 
 #[tokio::main]
 async fn main() {
-    dotenv().ok();
-    tracing_subscriber::fmt().init();
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(error) => {
+            eprint!("{}", error);
+            std::process::exit(1);
+        }
+    };
+    tracing_subscriber::fmt().with_max_level(config.service.log_level).init();
+
+    let pg_pool = match db::pool::create_pool(&config.service.database).await {
+        Ok(pg_pool) => pg_pool,
+        Err(error) => {
+            tracing::error!("failed to connect to database: {}", error);
+            std::process::exit(1);
+        }
+    };
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().is_some_and(|command| command == "migrate") {
+        if let Err(error) = db::migrations::command(&args[1..], &pg_pool).await {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Set RUN_MIGRATIONS=false kalau migration dijalankan terpisah, misalnya lewat job sebelum deploy
+    if config.run_migrations {
+        if let Err(error) = db::migrations::run(&pg_pool).await {
+            tracing::error!("failed to run migrations: {}", error);
+            std::process::exit(1);
+        }
+    }
+    let cache = redis::create_cache(&config.cache, config.service.redis.as_ref());
+
+    let storage = match storage::create_storage(&config.storage) {
+        Ok(storage) => storage,
+        Err(error) => {
+            tracing::error!("failed to create object storage: {}", error);
+            std::process::exit(1);
+        }
+    };
+    let thumbnail_sizes = config.thumbnail_sizes.clone();
+
+    let (shutdown_sender, shutdown) = watch::channel(false);
+    let mut background_tasks = vec![
+        services::inventory_service::spawn_expiry_task(pg_pool.clone(), cache.clone(), shutdown.clone()),
+        services::product_price_service::spawn_price_scheduler(pg_pool.clone(), cache.clone(), shutdown.clone()),
+    ];
+
+    let publisher = events::create_publisher(config.event_channel_capacity);
+    let Publisher::Channel(channel) = &publisher;
+    background_tasks.push(events::channel::spawn_log_subscriber(channel, shutdown.clone()));
 
-    let pg_pool = db::pool::create_pool(&std::env::var("DATABASE_URL").unwrap_or_default()).await;
-    // let redis_pool = redis::pool::create_redis_pool(&std::env::var("REDIS_URL").unwrap());
+    let orders = match orders::create_order_client(config.order_service.as_ref()) {
+        Ok(orders) => orders,
+        Err(error) => {
+            tracing::error!("failed to create order service client: {}", error);
+            std::process::exit(1);
+        }
+    };
+    // Tanpa order service tidak ada ulasan yang bisa lolos kategori yang mewajibkan verified purchase
+    if !orders.verifies_purchases() {
+        match services::category_service::any_requires_verified_purchase(&pg_pool).await {
+            Ok(false) => {}
+            Ok(true) => {
+                tracing::error!("ORDER_SERVICE_URL is required because some categories require a verified purchase for reviews");
+                std::process::exit(1);
+            }
+            Err(error) => {
+                tracing::error!("failed to check category review policies: {}", error);
+                std::process::exit(1);
+            }
+        }
+    }
 
-    let app_state = Arc::new(AppState { pg_pool });
+    let mut health = Health::new(config.service.health_check_timeout);
+    let db = pg_pool.clone();
+    health = health.with_check("postgres", move || {
+        let db = db.clone();
+        async move {
+            sqlx::query("SELECT 1").execute(&db).await.map(|_| ()).map_err(|error| error.to_string())
+        }
+    });
+    if config.service.redis.is_some() {
+        // Cache punya fallback ke database, jadi Redis mati tidak membuat service tidak siap
+        let redis = cache.clone();
+        health = health.with_optional_check("redis", move || {
+            let redis = redis.clone();
+            async move { redis.ping().await }
+        });
+    }
 
-    let app = routes::create_routes().layer(Extension(app_state));
+    let mut app = routes::create_routes().merge(common::health::routes(health.clone()));
+    if let Storage::Local(local) = &storage {
+        app = app.nest_service("/media", ServeDir::new(local.root()));
+    }
+
+    let app_state = Arc::new(AppState { pg_pool: pg_pool.clone(), storage, thumbnail_sizes, publisher, orders, cache });
+
+    let app = app.layer(Extension(app_state));
 
     // let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
+    let listener = match tokio::net::TcpListener::bind(config.service.bind_address).await {
+        Ok(listener) => listener,
+        Err(error) => {
+            tracing::error!("failed to bind {}: {}", config.service.bind_address, error);
+            std::process::exit(1);
+        }
+    };
 
     // Server::bind(&addr).serve(app.into_make_service()).await.unwrap();
-    axum::serve(listener, app.into_make_service()).await.unwrap();
+    let mut server_shutdown = shutdown.clone();
+    let mut server = tokio::spawn(axum::serve(listener, app.into_make_service())
+        .with_graceful_shutdown(async move {
+            let _ = server_shutdown.wait_for(|stopping| *stopping).await;
+        })
+        .into_future());
+
+    tokio::select! {
+        result = &mut server => {
+            tracing::error!("server stopped unexpectedly: {:?}", result);
+            std::process::exit(1);
+        }
+        _ = common::shutdown::signal() => {}
+    }
+
+    // Readiness langsung 503, tapi listener masih melayani selama drain delay supaya load balancer sempat
+    // mengeluarkan pod ini dari endpoint. Setelah itu listener berhenti menerima koneksi baru; request yang sedang
+    // jalan ditunggu sampai batas grace period, begitu juga background task, baru koneksi database ditutup
+    health.set_draining();
+    if !config.service.shutdown_drain_delay.is_zero() {
+        tracing::info!("draining, closing the listener in {}s", config.service.shutdown_drain_delay.as_secs());
+        tokio::select! {
+            _ = tokio::time::sleep(config.service.shutdown_drain_delay) => {}
+            _ = common::shutdown::signal() => tracing::info!("skipping drain delay"),
+        }
+    }
+    let _ = shutdown_sender.send(true);
+    tracing::info!("shutting down, waiting up to {}s for in-flight requests", config.service.shutdown_grace_period.as_secs());
+    let deadline = Instant::now() + config.service.shutdown_grace_period;
+
+    if tokio::time::timeout_at(deadline, &mut server).await.is_err() {
+        tracing::warn!("grace period elapsed, aborting remaining requests");
+        server.abort();
+    }
+    for mut task in background_tasks {
+        if tokio::time::timeout_at(deadline, &mut task).await.is_err() {
+            tracing::warn!("grace period elapsed, aborting background task");
+            task.abort();
+        }
+    }
+
+    // close menunggu semua koneksi dikembalikan, koneksi milik request yang di-abort bisa saja tidak kembali
+    if tokio::time::timeout(POOL_CLOSE_TIMEOUT, pg_pool.close()).await.is_err() {
+        tracing::warn!("timed out closing database connections");
+    }
+    tracing::info!("shutdown complete");
 }
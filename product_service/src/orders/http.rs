@@ -0,0 +1,46 @@
+use std::io;
+use std::time::Duration;
+use reqwest::Client;
+use serde::Deserialize;
+use uuid::Uuid;
+use crate::orders::OrderClient;
+
+#[derive(Deserialize)]
+struct DeliveredOrderResponse {
+    data: Option<bool>,
+}
+
+#[derive(Clone)]
+pub struct HttpOrderClient {
+    client: Client,
+    base_url: String,
+}
+
+impl HttpOrderClient {
+    pub fn new(base_url: &str, timeout_ms: u64) -> Result<Self, reqwest::Error> {
+        let client = Client::builder()
+            .timeout(Duration::from_millis(timeout_ms))
+            .build()?;
+
+        Ok(HttpOrderClient {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        })
+    }
+}
+
+impl OrderClient for HttpOrderClient {
+    async fn has_delivered_order(&self, user_id: Uuid, product_id: Uuid) -> io::Result<bool> {
+        let response = self.client.get(format!("{}/orders/delivered", self.base_url))
+            .query(&[("user_id", user_id), ("product_id", product_id)])
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(io::Error::other)?;
+
+        let body = response.json::<DeliveredOrderResponse>()
+            .await
+            .map_err(io::Error::other)?;
+        Ok(body.data.unwrap_or(false))
+    }
+}
\ No newline at end of file
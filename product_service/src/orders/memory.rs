@@ -0,0 +1,47 @@
+use std::collections::HashSet;
+use std::io;
+use std::sync::Arc;
+use uuid::Uuid;
+use crate::orders::OrderClient;
+
+/// Pengganti order service untuk pengembangan lokal dan pengujian, berisi pasangan (user_id, product_id) yang sudah diterima.
+#[derive(Clone)]
+pub struct InMemoryOrderClient {
+    deliveries: Arc<HashSet<(Uuid, Uuid)>>,
+}
+
+impl InMemoryOrderClient {
+    pub fn new(deliveries: impl IntoIterator<Item = (Uuid, Uuid)>) -> Self {
+        InMemoryOrderClient {
+            deliveries: Arc::new(deliveries.into_iter().collect()),
+        }
+    }
+}
+
+impl OrderClient for InMemoryOrderClient {
+    async fn has_delivered_order(&self, user_id: Uuid, product_id: Uuid) -> io::Result<bool> {
+        Ok(self.deliveries.contains(&(user_id, product_id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn reports_only_listed_deliveries() {
+        let user_id = Uuid::new_v4();
+        let product_id = Uuid::new_v4();
+        let client = InMemoryOrderClient::new([(user_id, product_id)]);
+
+        assert!(client.has_delivered_order(user_id, product_id).await.unwrap());
+        assert!(!client.has_delivered_order(user_id, Uuid::new_v4()).await.unwrap());
+        assert!(!client.has_delivered_order(Uuid::new_v4(), product_id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn empty_client_has_no_deliveries() {
+        let client = InMemoryOrderClient::new([]);
+        assert!(!client.has_delivered_order(Uuid::new_v4(), Uuid::new_v4()).await.unwrap());
+    }
+}
@@ -0,0 +1,44 @@
+pub mod http;
+pub mod memory;
+
+use std::io;
+use uuid::Uuid;
+use crate::config::OrderServiceConfig;
+use crate::orders::http::HttpOrderClient;
+use crate::orders::memory::InMemoryOrderClient;
+
+pub trait OrderClient {
+    async fn has_delivered_order(&self, user_id: Uuid, product_id: Uuid) -> io::Result<bool>;
+}
+
+#[derive(Clone)]
+pub enum Orders {
+    Http(HttpOrderClient),
+    InMemory(InMemoryOrderClient),
+}
+
+impl Orders {
+    /// Hanya order service sungguhan yang bisa membuktikan pembelian, client in-memory selalu kosong di luar pengujian.
+    pub fn verifies_purchases(&self) -> bool {
+        matches!(self, Orders::Http(_))
+    }
+}
+
+impl OrderClient for Orders {
+    async fn has_delivered_order(&self, user_id: Uuid, product_id: Uuid) -> io::Result<bool> {
+        match self {
+            Orders::Http(client) => client.has_delivered_order(user_id, product_id).await,
+            Orders::InMemory(client) => client.has_delivered_order(user_id, product_id).await,
+        }
+    }
+}
+
+pub fn create_order_client(config: Option<&OrderServiceConfig>) -> Result<Orders, reqwest::Error> {
+    match config {
+        Some(config) => Ok(Orders::Http(HttpOrderClient::new(&config.url, config.timeout_ms)?)),
+        None => {
+            tracing::warn!("ORDER_SERVICE_URL is not set, reviews will not be marked as verified purchases");
+            Ok(Orders::InMemory(InMemoryOrderClient::new([])))
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use redis::AsyncCommands;
+use redis::aio::ConnectionManager;
+use redis::RedisResult;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use uuid::Uuid;
+use crate::redis::pool::RedisPool;
+
+const KEY_PREFIX: &str = "product_service";
+const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(25);
+// TTL ditambah acak sampai 10% supaya key yang diisi bersamaan tidak kedaluwarsa bersamaan
+const TTL_JITTER_PERCENT: u64 = 10;
+
+pub fn product_key(id: Uuid) -> String {
+    format!("{}:product:{}", KEY_PREFIX, id)
+}
+
+pub fn product_with_reviews_key(id: Uuid) -> String {
+    format!("{}:product:{}:reviews", KEY_PREFIX, id)
+}
+
+pub fn categories_key(include_deleted: bool) -> String {
+    format!("{}:categories:{}", KEY_PREFIX, if include_deleted { "all" } else { "active" })
+}
+
+type InFlight = Arc<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>>;
+
+/// Menghapus penanda loader dari map setelah selesai, termasuk saat keluar lewat early return.
+struct InFlightGuard<'a> {
+    in_flight: &'a InFlight,
+    key: &'a str,
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut in_flight) = self.in_flight.lock() {
+            in_flight.remove(self.key);
+        }
+    }
+}
+
+/// Read-through cache di atas Redis. Semua error Redis hanya di-log dan dianggap cache miss,
+/// jadi request tetap dilayani dari database saat Redis mati.
+#[derive(Clone)]
+pub struct Cache {
+    pool: Option<RedisPool>,
+    pub product_ttl: Duration,
+    pub category_ttl: Duration,
+    lock_ttl: Duration,
+    in_flight: InFlight,
+}
+
+impl Cache {
+    pub fn new(pool: Option<RedisPool>, product_ttl: Duration, category_ttl: Duration, lock_ttl: Duration) -> Self {
+        Cache {
+            pool,
+            product_ttl,
+            category_ttl,
+            lock_ttl,
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub async fn get_or_load<T, E, F, Fut>(&self, key: &str, ttl: Duration, load: F) -> Result<T, E>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let Some(pool) = &self.pool else {
+            return load().await;
+        };
+        let Some(mut connection) = pool.get().await else {
+            return load().await;
+        };
+        match pool.run(read(&mut connection, key)).await {
+            Ok(Some(value)) => return Ok(value),
+            Ok(None) => {}
+            Err(error) => {
+                tracing::warn!("cache read for {} failed, falling back to database: {}", key, error);
+                return load().await;
+            }
+        }
+
+        // Request paralel untuk key yang sama di instance ini menunggu satu loader saja
+        let flight = self.in_flight.lock()
+            .map(|mut in_flight| in_flight.entry(key.to_string()).or_default().clone())
+            .unwrap_or_default();
+        let _flight = flight.lock().await;
+        let _guard = InFlightGuard { in_flight: &self.in_flight, key };
+        if let Ok(Some(value)) = pool.run(read(&mut connection, key)).await {
+            return Ok(value);
+        }
+
+        // Antar instance: hanya pemegang lock yang membaca database, sisanya menunggu cache terisi
+        let lock_key = format!("{}:lock", key);
+        let locked = self.try_lock(pool, &mut connection, &lock_key).await;
+        if !locked {
+            if let Some(value) = self.wait_for(pool, &mut connection, key).await {
+                return Ok(value);
+            }
+        }
+
+        let result = load().await;
+        if let Ok(value) = &result {
+            if let Err(error) = pool.run(write(&mut connection, key, value, with_jitter(ttl))).await {
+                tracing::warn!("cache write for {} failed: {}", key, error);
+            }
+        }
+        if locked {
+            let _: RedisResult<()> = pool.run(connection.del(&lock_key)).await;
+        }
+        result
+    }
+
+    /// Dipakai readiness check; Ok juga saat cache dimatikan karena Redis tidak dikonfigurasi.
+    pub async fn ping(&self) -> Result<(), String> {
+        match &self.pool {
+            Some(pool) => pool.ping().await,
+            None => Ok(()),
+        }
+    }
+
+    pub async fn invalidate(&self, keys: &[String]) {
+        let Some(pool) = &self.pool else {
+            return;
+        };
+        let Some(mut connection) = pool.get().await else {
+            return;
+        };
+        if let Err(error) = pool.run(connection.del::<_, ()>(keys)).await {
+            tracing::warn!("cache invalidation for {:?} failed: {}", keys, error);
+        }
+    }
+
+    pub async fn invalidate_product(&self, id: Uuid) {
+        self.invalidate_products(&[id]).await;
+    }
+
+    pub async fn invalidate_products(&self, ids: &[Uuid]) {
+        if ids.is_empty() {
+            return;
+        }
+        let keys: Vec<String> = ids.iter()
+            .flat_map(|id| [product_key(*id), product_with_reviews_key(*id)])
+            .collect();
+        self.invalidate(&keys).await;
+    }
+
+    pub async fn invalidate_categories(&self) {
+        self.invalidate(&[categories_key(false), categories_key(true)]).await;
+    }
+
+    async fn try_lock(&self, pool: &RedisPool, connection: &mut ConnectionManager, lock_key: &str) -> bool {
+        let command = redis::cmd("SET")
+            .arg(lock_key)
+            .arg(1)
+            .arg("NX")
+            .arg("PX")
+            .arg(self.lock_ttl.as_millis() as u64)
+            .to_owned();
+        let acquired: RedisResult<Option<String>> = pool.run(command.query_async(connection)).await;
+        // Kalau perintah lock gagal, anggap saja kita pemegang lock daripada menunggu sia-sia
+        !matches!(acquired, Ok(None))
+    }
+
+    async fn wait_for<T: DeserializeOwned>(&self, pool: &RedisPool, connection: &mut ConnectionManager, key: &str) -> Option<T> {
+        let deadline = Instant::now() + self.lock_ttl;
+        while Instant::now() < deadline {
+            tokio::time::sleep(LOCK_POLL_INTERVAL).await;
+            match pool.run(read(connection, key)).await {
+                Ok(Some(value)) => return Some(value),
+                Ok(None) => {}
+                Err(_) => return None,
+            }
+        }
+        None
+    }
+}
+
+async fn read<T: DeserializeOwned>(connection: &mut ConnectionManager, key: &str) -> RedisResult<Option<T>> {
+    let cached: Option<String> = connection.get(key).await?;
+    // Data lama yang formatnya sudah tidak cocok diperlakukan sebagai cache miss
+    Ok(cached.and_then(|cached| serde_json::from_str(&cached).ok()))
+}
+
+async fn write<T: Serialize>(connection: &mut ConnectionManager, key: &str, value: &T, ttl: Duration) -> RedisResult<()> {
+    let Ok(encoded) = serde_json::to_string(value) else {
+        return Ok(());
+    };
+    connection.pset_ex(key, encoded, ttl.as_millis() as u64).await
+}
+
+fn with_jitter(ttl: Duration) -> Duration {
+    let max_jitter = ttl.as_millis() as u64 * TTL_JITTER_PERCENT / 100;
+    if max_jitter == 0 {
+        return ttl;
+    }
+    ttl + Duration::from_millis((Uuid::new_v4().as_u128() % u128::from(max_jitter)) as u64)
+}
\ No newline at end of file
@@ -1 +1,23 @@
-pub mod pool;
\ No newline at end of file
+pub mod cache;
+pub mod pool;
+
+use common::config::RedisConfig;
+use crate::config::CacheConfig;
+use crate::redis::cache::Cache;
+
+pub fn create_cache(config: &CacheConfig, redis: Option<&RedisConfig>) -> Cache {
+    let pool = match redis {
+        Some(redis) => match pool::create_redis_pool(&redis.url, redis.timeout) {
+            Ok(pool) => Some(pool),
+            Err(error) => {
+                tracing::warn!("invalid REDIS_URL, caching is disabled: {}", error);
+                None
+            }
+        },
+        None => {
+            tracing::warn!("REDIS_URL is not set, caching is disabled");
+            None
+        }
+    };
+    Cache::new(pool, config.product_ttl, config.category_ttl, config.lock_ttl)
+}
\ No newline at end of file
@@ -0,0 +1,90 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use redis::aio::{ConnectionManager, ConnectionManagerConfig};
+use redis::{Client, ErrorKind, RedisError, RedisResult};
+use tokio::sync::Mutex;
+
+// Jeda sebelum mencoba konek ulang supaya request tidak menunggu timeout terus saat Redis mati
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Default)]
+struct PoolState {
+    connection: Option<ConnectionManager>,
+    last_attempt: Option<Instant>,
+}
+
+/// Koneksi Redis dibuat saat pertama kali dipakai, jadi service tetap bisa start walaupun Redis sedang mati.
+#[derive(Clone)]
+pub struct RedisPool {
+    client: Client,
+    timeout: Duration,
+    state: Arc<Mutex<PoolState>>,
+}
+
+impl RedisPool {
+    pub async fn get(&self) -> Option<ConnectionManager> {
+        let mut state = self.state.lock().await;
+        if let Some(connection) = &state.connection {
+            return Some(connection.clone());
+        }
+        if state.last_attempt.is_some_and(|last_attempt| last_attempt.elapsed() < RECONNECT_INTERVAL) {
+            return None;
+        }
+
+        // ConnectionManager menyambung ulang sendiri setelah koneksi pertama berhasil
+        let config = ConnectionManagerConfig::new()
+            .set_connection_timeout(self.timeout)
+            .set_response_timeout(self.timeout)
+            .set_number_of_retries(1);
+        let connecting = ConnectionManager::new_with_config(self.client.clone(), config);
+        match tokio::time::timeout(self.timeout, connecting).await {
+            Ok(Ok(connection)) => {
+                state.connection = Some(connection.clone());
+                Some(connection)
+            }
+            Ok(Err(error)) => {
+                tracing::warn!("failed to connect to redis: {}", error);
+                state.last_attempt = Some(Instant::now());
+                None
+            }
+            Err(_) => {
+                tracing::warn!("failed to connect to redis: timed out");
+                state.last_attempt = Some(Instant::now());
+                None
+            }
+        }
+    }
+
+    pub async fn ping(&self) -> Result<(), String> {
+        let Some(mut connection) = self.get().await else {
+            return Err("not connected, waiting to reconnect".to_string());
+        };
+        self.run(redis::cmd("PING").query_async(&mut connection)).await
+            .map_err(|error| error.to_string())
+    }
+
+    /// Menjalankan perintah dengan batas waktu. Kalau koneksi bermasalah, Redis dilewati sampai RECONNECT_INTERVAL lewat.
+    pub async fn run<T>(&self, operation: impl Future<Output = RedisResult<T>>) -> RedisResult<T> {
+        let result = match tokio::time::timeout(self.timeout, operation).await {
+            Ok(result) => result,
+            Err(_) => Err(RedisError::from((ErrorKind::IoError, "redis command timed out"))),
+        };
+        if let Err(error) = &result {
+            if error.is_io_error() || error.is_timeout() || error.is_connection_refusal() || error.is_connection_dropped() {
+                let mut state = self.state.lock().await;
+                state.connection = None;
+                state.last_attempt = Some(Instant::now());
+            }
+        }
+        result
+    }
+}
+
+pub fn create_redis_pool(redis_url: &str, timeout: Duration) -> RedisResult<RedisPool> {
+    Ok(RedisPool {
+        client: Client::open(redis_url)?,
+        timeout,
+        state: Arc::new(Mutex::new(PoolState::default())),
+    })
+}
\ No newline at end of file
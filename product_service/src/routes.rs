@@ -6,4 +6,9 @@ pub fn create_routes() -> Router {
         .nest("/categories", handlers::category_handler::routes())
         .nest("/products", handlers::product_handler::routes())
         .nest("/products", handlers::product_review_handler::routes())
+        .nest("/products", handlers::product_variant_handler::routes())
+        .nest("/products", handlers::product_image_handler::routes())
+        .nest("/products", handlers::stock_movement_handler::routes())
+        .nest("/products", handlers::product_price_handler::routes())
+        .nest("/inventory", handlers::inventory_handler::routes())
 }
\ No newline at end of file
@@ -1,43 +1,164 @@
+use std::collections::HashMap;
 use sqlx::{Error, Pool, Postgres};
 use sqlx::types::chrono::Utc;
 use uuid::Uuid;
-use crate::domain::category::{Category};
+use crate::domain::category::{Category, CategoryNode, CategoryReviewPolicy, UpdateCategoryReviewPolicy};
+use crate::services::error::ServiceError;
 use crate::services::service::CategoryService;
 
 pub struct CategoryServiceImpl;
 
+fn build_tree(categories: Vec<Category>, root_id: Option<Uuid>) -> Vec<CategoryNode> {
+    let mut children_by_parent: HashMap<Option<Uuid>, Vec<Category>> = HashMap::new();
+    for category in categories {
+        children_by_parent.entry(category.parent_id).or_default().push(category);
+    }
+    attach_children(&mut children_by_parent, root_id)
+}
+
+fn attach_children(children_by_parent: &mut HashMap<Option<Uuid>, Vec<Category>>, parent_id: Option<Uuid>) -> Vec<CategoryNode> {
+    let children = children_by_parent.remove(&parent_id).unwrap_or_default();
+    children.into_iter()
+        .map(|category| CategoryNode {
+            id: category.id,
+            name: category.name,
+            parent_id: category.parent_id,
+            children: attach_children(children_by_parent, Some(category.id)),
+            version: category.version,
+            created_at: category.created_at,
+            updated_at: category.updated_at,
+        })
+        .collect()
+}
+
+/// Kebijakan ulasan berlaku juga untuk semua subkategori, jadi cukup satu leluhur yang mewajibkan.
+pub async fn requires_verified_purchase(category_id: Uuid, pool: &Pool<Postgres>) -> Result<bool, Error> {
+    let required = sqlx::query_scalar!(r#"
+            WITH RECURSIVE ancestors AS (
+                SELECT id, parent_id, require_verified_purchase FROM categories WHERE id = $1
+                UNION
+                SELECT c.id, c.parent_id, c.require_verified_purchase FROM categories c
+                INNER JOIN ancestors ON c.id = ancestors.parent_id
+            )
+            SELECT COALESCE(bool_or(require_verified_purchase), FALSE) AS "required!" FROM ancestors"#, category_id)
+        .fetch_one(pool)
+        .await?;
+    Ok(required)
+}
+
+/// Produk menyimpan nama kategorinya di cache, termasuk produk yang diarsipkan.
+pub async fn product_ids(category_id: Uuid, pool: &Pool<Postgres>) -> Result<Vec<Uuid>, Error> {
+    let ids = sqlx::query_scalar!("SELECT id FROM products WHERE category_id = $1", category_id)
+        .fetch_all(pool)
+        .await?;
+    Ok(ids)
+}
+
+pub async fn any_requires_verified_purchase(pool: &Pool<Postgres>) -> Result<bool, Error> {
+    let required = sqlx::query_scalar!(r#"SELECT EXISTS (SELECT 1 FROM categories WHERE require_verified_purchase) AS "exists!""#)
+        .fetch_one(pool)
+        .await?;
+    Ok(required)
+}
 
 impl CategoryService for CategoryServiceImpl {
-    async fn fetch_categories(&self, pool: &Pool<Postgres>) -> Result<Vec<Category>, Error> {
-        let categories = sqlx::query_as!(Category, "SELECT id, name, created_at, updated_at FROM categories")
+    async fn fetch_categories(&self, include_deleted: bool, pool: &Pool<Postgres>) -> Result<Vec<Category>, Error> {
+        let categories = sqlx::query_as!(Category, "SELECT id, name, parent_id, version, created_at, updated_at, deleted_at FROM categories WHERE $1 OR deleted_at IS NULL", include_deleted)
             .fetch_all(pool)
             .await?;
         Ok(categories)
     }
 
     async fn fetch_by_id(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<Category, Error> {
-        let category = sqlx::query_as!(Category, "SELECT id, name, created_at, updated_at FROM categories WHERE id = $1", id)
+        let category = sqlx::query_as!(Category, "SELECT id, name, parent_id, version, created_at, updated_at, deleted_at FROM categories WHERE id = $1", id)
             .fetch_one(pool)
             .await?;
         Ok(category)
     }
 
-    async fn save(&self, name: String, pool: &Pool<Postgres>) -> Result<Category, Error> {
+    async fn fetch_tree(&self, pool: &Pool<Postgres>) -> Result<Vec<CategoryNode>, Error> {
+        let categories = sqlx::query_as!(Category, "SELECT id, name, parent_id, version, created_at, updated_at, deleted_at FROM categories WHERE deleted_at IS NULL ORDER BY name")
+            .fetch_all(pool)
+            .await?;
+        Ok(build_tree(categories, None))
+    }
+
+    async fn fetch_subtree(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<CategoryNode, Error> {
+        let root = self.fetch_by_id(id, pool).await?;
+        if root.deleted_at.is_some() {
+            return Err(Error::RowNotFound);
+        }
+
+        let descendants = sqlx::query_as!(Category, r#"
+                WITH RECURSIVE subtree AS (
+                    SELECT id, name, parent_id, version, created_at, updated_at, deleted_at FROM categories WHERE parent_id = $1 AND deleted_at IS NULL
+                    UNION ALL
+                    SELECT c.id, c.name, c.parent_id, c.version, c.created_at, c.updated_at, c.deleted_at FROM categories c
+                    INNER JOIN subtree ON c.parent_id = subtree.id
+                    WHERE c.deleted_at IS NULL
+                )
+                SELECT id AS "id!", name AS "name!", parent_id, version AS "version!", created_at AS "created_at!", updated_at AS "updated_at!", deleted_at
+                FROM subtree ORDER BY name"#, id)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(CategoryNode {
+            id: root.id,
+            name: root.name,
+            parent_id: root.parent_id,
+            children: build_tree(descendants, Some(root.id)),
+            version: root.version,
+            created_at: root.created_at,
+            updated_at: root.updated_at,
+        })
+    }
+
+    async fn fetch_breadcrumb(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<Vec<Category>, Error> {
+        let breadcrumb = sqlx::query_as!(Category, r#"
+                WITH RECURSIVE ancestors AS (
+                    SELECT id, name, parent_id, version, created_at, updated_at, deleted_at, 0 AS depth FROM categories WHERE id = $1
+                    UNION ALL
+                    SELECT c.id, c.name, c.parent_id, c.version, c.created_at, c.updated_at, c.deleted_at, ancestors.depth + 1 FROM categories c
+                    INNER JOIN ancestors ON c.id = ancestors.parent_id
+                )
+                SELECT id AS "id!", name AS "name!", parent_id, version AS "version!", created_at AS "created_at!", updated_at AS "updated_at!", deleted_at
+                FROM ancestors ORDER BY depth DESC"#, id)
+            .fetch_all(pool)
+            .await?;
+
+        if breadcrumb.is_empty() {
+            return Err(Error::RowNotFound);
+        }
+        Ok(breadcrumb)
+    }
+
+    async fn save(&self, name: String, parent_id: Option<Uuid>, pool: &Pool<Postgres>) -> Result<Category, Error> {
+        if let Some(parent_id) = parent_id {
+            let parent = self.fetch_by_id(parent_id, pool).await?;
+            if parent.deleted_at.is_some() {
+                return Err(Error::Protocol("parent category is archived".to_string()));
+            }
+        }
+
         let now = Utc::now().timestamp_millis();
         let category = Category {
             id: Uuid::new_v4(),
             name,
+            parent_id,
+            version: 0,
             created_at: now,
             updated_at: now,
+            deleted_at: None,
         };
 
         sqlx::query!(
-        "INSERT INTO categories (id, name, created_at, updated_at, version) VALUES ($1, $2, $3, $4, $5)",
+        "INSERT INTO categories (id, name, parent_id, created_at, updated_at, version) VALUES ($1, $2, $3, $4, $5, $6)",
         category.id,
         category.name,
+        category.parent_id,
         category.created_at,
         category.updated_at,
-            0
+        category.version
         )
             .execute(pool)
             .await?;
@@ -45,37 +166,146 @@ impl CategoryService for CategoryServiceImpl {
         Ok(category)
     }
 
-    async fn update(&self, id: Uuid, name: String, pool: &Pool<Postgres>) -> Result<Category, Error> {
-        let mut category = sqlx::query_as!(Category, "SELECT id, name, created_at, updated_at FROM categories WHERE id = $1", id)
-            .fetch_one(pool)
+    async fn update(&self, id: Uuid, name: String, parent_id: Option<Option<Uuid>>, version: i32, pool: &Pool<Postgres>) -> Result<Category, ServiceError> {
+        let mut tx = pool.begin().await?;
+
+        let mut category = sqlx::query_as!(Category, "SELECT id, name, parent_id, version, created_at, updated_at, deleted_at FROM categories WHERE id = $1 FOR UPDATE", id)
+            .fetch_one(&mut *tx)
             .await?;
 
+        if category.version != version {
+            return Err(ServiceError::VersionConflict { expected: version, current: category.version });
+        }
+
+        let parent_id = parent_id.unwrap_or(category.parent_id);
+        if let Some(new_parent_id) = parent_id.filter(|new_parent_id| category.parent_id != Some(*new_parent_id)) {
+            // Serialisasi perpindahan kategori supaya dua update paralel tidak membentuk siklus
+            sqlx::query!("SELECT pg_advisory_xact_lock(hashtext('categories_tree'))")
+                .execute(&mut *tx)
+                .await?;
+
+            let creates_cycle = sqlx::query_scalar!(r#"
+                    WITH RECURSIVE ancestors AS (
+                        SELECT id, parent_id FROM categories WHERE id = $1
+                        UNION
+                        SELECT c.id, c.parent_id FROM categories c
+                        INNER JOIN ancestors ON c.id = ancestors.parent_id
+                    )
+                    SELECT EXISTS (SELECT 1 FROM ancestors WHERE id = $2) AS "exists!""#, new_parent_id, id)
+                .fetch_one(&mut *tx)
+                .await?;
+
+            if creates_cycle {
+                return Err(Error::Protocol("category cannot be moved under itself or one of its descendants".to_string()).into());
+            }
+
+            let parent_archived = sqlx::query_scalar!("SELECT deleted_at IS NOT NULL AS \"archived!\" FROM categories WHERE id = $1", new_parent_id)
+                .fetch_one(&mut *tx)
+                .await?;
+            if parent_archived {
+                return Err(Error::Protocol("parent category is archived".to_string()).into());
+            }
+        }
+
         category.name = name;
+        category.parent_id = parent_id;
+        category.version += 1;
         category.updated_at = Utc::now().timestamp_millis();
 
         // Melakukan update di database
         sqlx::query!(
-            "UPDATE categories SET name = $1, updated_at = $2 WHERE id = $3",
+            "UPDATE categories SET name = $1, parent_id = $2, updated_at = $3, version = $4 WHERE id = $5",
             category.name,
+            category.parent_id,
             category.updated_at,
+            category.version,
             id
         )
-            .execute(pool)
+            .execute(&mut *tx)
             .await?;
 
+        tx.commit().await?;
         Ok(category)
     }
 
     async fn delete(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<(), Error> {
-        let delete = sqlx::query!("DELETE FROM categories WHERE id = $1", id)
-            .execute(pool)
-            .await;
+        let mut tx = pool.begin().await?;
 
-        match delete.unwrap_or_default().rows_affected() {
-            1 => Ok(()),
+        // Kategori yang masih dipakai tidak boleh diarsipkan, sama seperti foreign key saat hard delete
+        let in_use = sqlx::query_scalar!(r#"
+                SELECT EXISTS (SELECT 1 FROM categories WHERE parent_id = $1 AND deleted_at IS NULL)
+                OR EXISTS (SELECT 1 FROM products WHERE category_id = $1 AND deleted_at IS NULL) AS "in_use!""#, id)
+            .fetch_one(&mut *tx)
+            .await?;
+        if in_use {
+            return Err(Error::Protocol("category still has active subcategories or products".to_string()));
+        }
+
+        let now = Utc::now().timestamp_millis();
+        let archive = sqlx::query!(
+            "UPDATE categories SET deleted_at = $1, updated_at = $1, version = version + 1 WHERE id = $2 AND deleted_at IS NULL",
+            now,
+            id
+        )
+            .execute(&mut *tx)
+            .await?;
+
+        match archive.rows_affected() {
+            1 => {
+                tx.commit().await?;
+                Ok(())
+            }
             _ => {
                 Err(Error::RowNotFound)
             }
         }
     }
-}
\ No newline at end of file
+
+    async fn restore(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<Category, Error> {
+        let category = self.fetch_by_id(id, pool).await?;
+        if category.deleted_at.is_none() {
+            return Err(Error::Protocol("category is not archived".to_string()));
+        }
+        if let Some(parent_id) = category.parent_id {
+            if self.fetch_by_id(parent_id, pool).await?.deleted_at.is_some() {
+                return Err(Error::Protocol("parent category is archived, restore it first".to_string()));
+            }
+        }
+
+        let category = sqlx::query_as!(Category, r#"
+                UPDATE categories SET deleted_at = NULL, updated_at = $1, version = version + 1
+                WHERE id = $2 AND deleted_at IS NOT NULL
+                RETURNING id, name, parent_id, version, created_at, updated_at, deleted_at"#,
+            Utc::now().timestamp_millis(), id)
+            .fetch_one(pool)
+            .await?;
+        Ok(category)
+    }
+    async fn fetch_review_policy(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<CategoryReviewPolicy, Error> {
+        let require_verified_purchase = sqlx::query_scalar!("SELECT require_verified_purchase FROM categories WHERE id = $1", id)
+            .fetch_one(pool)
+            .await?;
+
+        Ok(CategoryReviewPolicy {
+            category_id: id,
+            require_verified_purchase,
+            inherited_require_verified_purchase: requires_verified_purchase(id, pool).await?,
+        })
+    }
+
+    async fn update_review_policy(&self, id: Uuid, request: UpdateCategoryReviewPolicy, pool: &Pool<Postgres>) -> Result<CategoryReviewPolicy, Error> {
+        let update = sqlx::query!(
+            "UPDATE categories SET require_verified_purchase = $1, updated_at = $2 WHERE id = $3",
+            request.require_verified_purchase,
+            Utc::now().timestamp_millis(),
+            id
+        )
+            .execute(pool)
+            .await?;
+        if update.rows_affected() != 1 {
+            return Err(Error::RowNotFound);
+        }
+
+        self.fetch_review_policy(id, pool).await
+    }
+}
@@ -0,0 +1,20 @@
+use thiserror::Error;
+use common::validation::ValidationErrors;
+
+#[derive(Error, Debug)]
+pub enum ServiceError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("version conflict: expected version {expected} but current version is {current}")]
+    VersionConflict { expected: i32, current: i32 },
+    #[error("insufficient stock: requested {requested} but only {available} available")]
+    InsufficientStock { requested: i32, available: i32 },
+    #[error("{0}")]
+    Forbidden(String),
+    #[error(transparent)]
+    Validation(#[from] ValidationErrors),
+    #[error("{0}")]
+    Conflict(String),
+    #[error("{0}")]
+    Unavailable(String),
+}
\ No newline at end of file
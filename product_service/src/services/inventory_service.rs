@@ -0,0 +1,186 @@
+use std::time::Duration;
+use sqlx::{Error, Pool, Postgres};
+use sqlx::types::chrono::Utc;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+use crate::domain::inventory::{CreateReservation, InventoryReservation, ReservationStatus};
+use crate::domain::stock_movement::StockMovementReason;
+use crate::events::{EventPublisher, Publisher};
+use crate::redis::cache::Cache;
+use crate::services::error::ServiceError;
+use crate::services::stock_movement_service;
+use crate::services::service::InventoryService;
+
+const DEFAULT_RESERVATION_TTL_SECONDS: i64 = 15 * 60;
+const MAX_RESERVATION_TTL_SECONDS: i64 = 24 * 60 * 60;
+const EXPIRY_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+pub struct InventoryServiceImpl;
+
+/// Menandai reservasi yang lewat TTL sebagai expired secara berkala.
+/// Reservasi expired sudah tidak dihitung walau sweeper belum jalan.
+pub fn spawn_expiry_task(pool: Pool<Postgres>, cache: Cache, mut shutdown: watch::Receiver<bool>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(EXPIRY_SWEEP_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown.changed() => break,
+            }
+            match InventoryServiceImpl.expire_reservations(&pool).await {
+                Ok(expired) if expired.is_empty() => {}
+                Ok(mut expired) => {
+                    tracing::info!("expired {} inventory reservations", expired.len());
+                    // Stok tersedia produk ikut berubah, jadi cache produknya dibuang
+                    expired.sort();
+                    expired.dedup();
+                    cache.invalidate_products(&expired).await;
+                }
+                Err(error) => tracing::warn!("failed to expire inventory reservations: {}", error),
+            }
+        }
+    })
+}
+
+impl InventoryService for InventoryServiceImpl {
+    async fn reserve(&self, request: CreateReservation, pool: &Pool<Postgres>) -> Result<InventoryReservation, ServiceError> {
+        if request.quantity <= 0 {
+            return Err(Error::Protocol("quantity must be greater than zero".to_string()).into());
+        }
+        if request.reference.trim().is_empty() {
+            return Err(Error::Protocol("reference must not be empty".to_string()).into());
+        }
+        let ttl_seconds = request.ttl_seconds.unwrap_or(DEFAULT_RESERVATION_TTL_SECONDS);
+        if !(1..=MAX_RESERVATION_TTL_SECONDS).contains(&ttl_seconds) {
+            return Err(Error::Protocol(format!("ttl_seconds must be between 1 and {}", MAX_RESERVATION_TTL_SECONDS)).into());
+        }
+
+        let mut tx = pool.begin().await?;
+        let now = Utc::now().timestamp_millis();
+
+        // Lock baris produk supaya reservasi paralel untuk produk yang sama antri
+        let stock = sqlx::query_scalar!("SELECT stock FROM products WHERE id = $1 AND deleted_at IS NULL FOR UPDATE", request.product_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        let reserved = sqlx::query_scalar!(r#"
+                SELECT COALESCE(SUM(quantity), 0)::integer AS "reserved!" FROM inventory_reservations
+                WHERE product_id = $1 AND status = 'active' AND expires_at > $2"#, request.product_id, now)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        let available = stock - reserved;
+        if request.quantity > available {
+            return Err(ServiceError::InsufficientStock { requested: request.quantity, available: available.max(0) });
+        }
+
+        let reservation = InventoryReservation {
+            id: Uuid::new_v4(),
+            product_id: request.product_id,
+            quantity: request.quantity,
+            reference: request.reference,
+            status: ReservationStatus::Active,
+            expires_at: now + ttl_seconds * 1000,
+            created_at: now,
+            updated_at: now,
+        };
+
+        sqlx::query!(
+        "INSERT INTO inventory_reservations (id, product_id, quantity, reference, status, expires_at, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+            reservation.id,
+            reservation.product_id,
+            reservation.quantity,
+            reservation.reference,
+            reservation.status as _,
+            reservation.expires_at,
+            reservation.created_at,
+            reservation.updated_at
+        ).execute(&mut *tx).await?;
+
+        tx.commit().await?;
+        Ok(reservation)
+    }
+
+    async fn fetch_reservation(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<InventoryReservation, Error> {
+        let reservation = sqlx::query_as!(InventoryReservation, r#"
+                SELECT id, product_id, quantity, reference, status AS "status: ReservationStatus", expires_at, created_at, updated_at
+                FROM inventory_reservations WHERE id = $1"#, id)
+            .fetch_one(pool)
+            .await?;
+        Ok(reservation)
+    }
+
+    async fn confirm(&self, id: Uuid, publisher: &Publisher, pool: &Pool<Postgres>) -> Result<InventoryReservation, ServiceError> {
+        let mut tx = pool.begin().await?;
+        let now = Utc::now().timestamp_millis();
+
+        let mut reservation = sqlx::query_as!(InventoryReservation, r#"
+                SELECT id, product_id, quantity, reference, status AS "status: ReservationStatus", expires_at, created_at, updated_at
+                FROM inventory_reservations WHERE id = $1 FOR UPDATE"#, id)
+            .fetch_one(&mut *tx)
+            .await?;
+        if reservation.status != ReservationStatus::Active {
+            return Err(Error::Protocol("only active reservations can be confirmed".to_string()).into());
+        }
+        if reservation.expires_at <= now {
+            return Err(Error::Protocol("reservation has expired".to_string()).into());
+        }
+
+        // Stok tidak boleh negatif, constraint di database jadi pengaman terakhir
+        let (_, event) = stock_movement_service::record_movement(
+            &mut tx,
+            reservation.product_id,
+            StockMovementReason::Sale,
+            -reservation.quantity,
+            "inventory",
+            Some(&reservation.reference),
+        ).await?;
+
+        reservation.status = ReservationStatus::Confirmed;
+        reservation.updated_at = now;
+        sqlx::query!(
+            "UPDATE inventory_reservations SET status = $1, updated_at = $2 WHERE id = $3",
+            reservation.status as _,
+            reservation.updated_at,
+            id
+        )
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        if let Some(event) = event {
+            publisher.publish(event).await;
+        }
+        Ok(reservation)
+    }
+
+    async fn release(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<InventoryReservation, Error> {
+        let released = sqlx::query_as!(InventoryReservation, r#"
+                UPDATE inventory_reservations SET status = 'released', updated_at = $1
+                WHERE id = $2 AND status = 'active'
+                RETURNING id, product_id, quantity, reference, status AS "status: ReservationStatus", expires_at, created_at, updated_at"#,
+            Utc::now().timestamp_millis(), id)
+            .fetch_optional(pool)
+            .await?;
+
+        match released {
+            Some(reservation) => Ok(reservation),
+            None => {
+                // Bedakan reservasi yang tidak ada dengan yang sudah tidak aktif
+                self.fetch_reservation(id, pool).await?;
+                Err(Error::Protocol("only active reservations can be released".to_string()))
+            }
+        }
+    }
+
+    async fn expire_reservations(&self, pool: &Pool<Postgres>) -> Result<Vec<Uuid>, Error> {
+        let now = Utc::now().timestamp_millis();
+        let expired = sqlx::query_scalar!(
+            "UPDATE inventory_reservations SET status = 'expired', updated_at = $1 WHERE status = 'active' AND expires_at <= $1 RETURNING product_id",
+            now
+        )
+            .fetch_all(pool)
+            .await?;
+        Ok(expired)
+    }
+}
\ No newline at end of file
@@ -1,4 +1,11 @@
 pub mod category_service;
+pub mod error;
 pub mod service;
+pub mod inventory_service;
 pub mod product_service;
-pub mod product_review_service;
\ No newline at end of file
+pub mod product_review_service;
+pub mod product_variant_service;
+pub mod product_image_service;
+pub mod product_import_service;
+pub mod product_price_service;
+pub mod stock_movement_service;
\ No newline at end of file
@@ -0,0 +1,261 @@
+use std::collections::{BTreeMap, HashSet};
+use std::io::Cursor;
+use bytes::Bytes;
+use image::{ImageFormat, ImageReader, Limits};
+use sqlx::{Error, Pool, Postgres};
+use sqlx::types::chrono::Utc;
+use sqlx::types::Json;
+use uuid::Uuid;
+use crate::domain::product_image::ProductImage;
+use crate::services::service::ProductImageService;
+use crate::storage::{ObjectStorage, Storage};
+
+// Batas dimensi dicek dari header sebelum decode, supaya file kecil tidak bisa memakan memori besar
+const MAX_IMAGE_DIMENSION: u32 = 8192;
+
+pub struct ProductImageServiceImpl;
+
+pub(crate) struct ProcessedImage {
+    pub(crate) format: ImageFormat,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) thumbnails: Vec<(u32, Vec<u8>)>,
+}
+
+fn original_key(product_id: Uuid, image_id: Uuid, format: ImageFormat) -> String {
+    let extension = format.extensions_str().first().copied().unwrap_or("bin");
+    format!("products/{}/{}/original.{}", product_id, image_id, extension)
+}
+
+fn thumbnail_key(product_id: Uuid, image_id: Uuid, size: u32) -> String {
+    format!("products/{}/{}/thumb_{}.jpg", product_id, image_id, size)
+}
+
+pub(crate) fn process_image(data: &[u8], thumbnail_sizes: &[u32]) -> Result<ProcessedImage, Error> {
+    let mut reader = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .map_err(Error::Io)?;
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_IMAGE_DIMENSION);
+    limits.max_image_height = Some(MAX_IMAGE_DIMENSION);
+    reader.limits(limits);
+
+    let format = match reader.format() {
+        Some(format @ (ImageFormat::Jpeg | ImageFormat::Png | ImageFormat::WebP)) => format,
+        _ => return Err(Error::Protocol("unsupported image format, expected JPEG, PNG or WebP".to_string())),
+    };
+    let image = reader.decode()
+        .map_err(|error| Error::Protocol(format!("invalid image: {}", error)))?;
+
+    let mut thumbnails = Vec::with_capacity(thumbnail_sizes.len());
+    for &size in thumbnail_sizes {
+        let thumbnail = if size >= image.width().max(image.height()) {
+            image.to_rgb8()
+        } else {
+            image.thumbnail(size, size).to_rgb8()
+        };
+        let mut encoded = Vec::new();
+        thumbnail.write_to(&mut Cursor::new(&mut encoded), ImageFormat::Jpeg)
+            .map_err(|error| Error::Protocol(format!("failed to encode thumbnail: {}", error)))?;
+        thumbnails.push((size, encoded));
+    }
+
+    Ok(ProcessedImage {
+        format,
+        width: image.width(),
+        height: image.height(),
+        thumbnails,
+    })
+}
+
+impl ProductImageService for ProductImageServiceImpl {
+    async fn fetch_images(&self, product_id: Uuid, pool: &Pool<Postgres>) -> Result<Vec<ProductImage>, Error> {
+        let images = sqlx::query_as!(ProductImage, r#"
+                SELECT id, product_id, storage_key, url, thumbnails AS "thumbnails: Json<BTreeMap<u32, String>>", content_type,
+                width, height, position, is_primary, created_at, updated_at
+                FROM product_images WHERE product_id = $1 ORDER BY position"#, product_id)
+            .fetch_all(pool)
+            .await?;
+        Ok(images)
+    }
+
+    async fn save_image(&self, product_id: Uuid, data: Bytes, storage: &Storage, thumbnail_sizes: &[u32], pool: &Pool<Postgres>) -> Result<ProductImage, Error> {
+        let product_exists = sqlx::query_scalar!(r#"SELECT EXISTS (SELECT 1 FROM products WHERE id = $1) AS "exists!""#, product_id)
+            .fetch_one(pool)
+            .await?;
+        if !product_exists {
+            return Err(Error::RowNotFound);
+        }
+
+        // Decode dan resize memakan CPU, jadi jangan jalankan di thread async
+        let sizes = thumbnail_sizes.to_vec();
+        let source = data.clone();
+        let processed = tokio::task::spawn_blocking(move || process_image(&source, &sizes))
+            .await
+            .map_err(|error| Error::Protocol(error.to_string()))??;
+
+        let id = Uuid::new_v4();
+        let storage_key = original_key(product_id, id, processed.format);
+        storage.put(&storage_key, data, processed.format.to_mime_type()).await?;
+
+        let mut thumbnails = BTreeMap::new();
+        for (size, encoded) in processed.thumbnails {
+            let key = thumbnail_key(product_id, id, size);
+            storage.put(&key, Bytes::from(encoded), ImageFormat::Jpeg.to_mime_type()).await?;
+            thumbnails.insert(size, storage.url(&key));
+        }
+
+        let now = Utc::now().timestamp_millis();
+        let mut image = ProductImage {
+            id,
+            product_id,
+            url: storage.url(&storage_key),
+            storage_key,
+            thumbnails: Json(thumbnails),
+            content_type: processed.format.to_mime_type().to_string(),
+            width: processed.width as i32,
+            height: processed.height as i32,
+            position: 0,
+            is_primary: false,
+            created_at: now,
+            updated_at: now,
+        };
+
+        // Gambar pertama otomatis jadi gambar utama dan ditaruh di urutan terakhir
+        let inserted = sqlx::query!(r#"
+                INSERT INTO product_images (id, product_id, storage_key, url, thumbnails, content_type, width, height, position, is_primary, created_at, updated_at)
+                SELECT $1, $2, $3, $4, $5, $6, $7, $8,
+                    COALESCE((SELECT MAX(position) + 1 FROM product_images WHERE product_id = $2), 0),
+                    NOT EXISTS (SELECT 1 FROM product_images WHERE product_id = $2 AND is_primary),
+                    $9, $10
+                RETURNING position, is_primary"#,
+            image.id,
+            image.product_id,
+            image.storage_key,
+            image.url,
+            &image.thumbnails as _,
+            image.content_type,
+            image.width,
+            image.height,
+            image.created_at,
+            image.updated_at
+        ).fetch_one(pool).await;
+
+        match inserted {
+            Ok(row) => {
+                image.position = row.position;
+                image.is_primary = row.is_primary;
+                Ok(image)
+            }
+            Err(error) => {
+                self.remove_objects(&image, storage).await;
+                Err(error)
+            }
+        }
+    }
+
+    async fn reorder(&self, product_id: Uuid, image_ids: Vec<Uuid>, pool: &Pool<Postgres>) -> Result<Vec<ProductImage>, Error> {
+        let mut tx = pool.begin().await?;
+
+        let existing: HashSet<Uuid> = sqlx::query_scalar!("SELECT id FROM product_images WHERE product_id = $1 FOR UPDATE", product_id)
+            .fetch_all(&mut *tx)
+            .await?
+            .into_iter()
+            .collect();
+        let requested: HashSet<Uuid> = image_ids.iter().copied().collect();
+        if requested.len() != image_ids.len() || requested != existing {
+            return Err(Error::Protocol("image_ids must list every image of the product exactly once".to_string()));
+        }
+
+        let now = Utc::now().timestamp_millis();
+        for (position, id) in image_ids.iter().enumerate() {
+            sqlx::query!("UPDATE product_images SET position = $1, updated_at = $2 WHERE id = $3", position as i32, now, id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        self.fetch_images(product_id, pool).await
+    }
+
+    async fn set_primary(&self, product_id: Uuid, id: Uuid, pool: &Pool<Postgres>) -> Result<Vec<ProductImage>, Error> {
+        let mut tx = pool.begin().await?;
+        let now = Utc::now().timestamp_millis();
+
+        sqlx::query!("UPDATE product_images SET is_primary = FALSE, updated_at = $1 WHERE product_id = $2 AND is_primary", now, product_id)
+            .execute(&mut *tx)
+            .await?;
+        let updated = sqlx::query!("UPDATE product_images SET is_primary = TRUE, updated_at = $1 WHERE id = $2 AND product_id = $3", now, id, product_id)
+            .execute(&mut *tx)
+            .await?;
+        if updated.rows_affected() != 1 {
+            return Err(Error::RowNotFound);
+        }
+
+        tx.commit().await?;
+        self.fetch_images(product_id, pool).await
+    }
+
+    async fn delete_image(&self, product_id: Uuid, id: Uuid, storage: &Storage, pool: &Pool<Postgres>) -> Result<(), Error> {
+        let mut tx = pool.begin().await?;
+
+        let image = sqlx::query_as!(ProductImage, r#"
+                DELETE FROM product_images WHERE id = $1 AND product_id = $2
+                RETURNING id, product_id, storage_key, url, thumbnails AS "thumbnails: Json<BTreeMap<u32, String>>", content_type,
+                width, height, position, is_primary, created_at, updated_at"#, id, product_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        if image.is_primary {
+            sqlx::query!(r#"
+                    UPDATE product_images SET is_primary = TRUE, updated_at = $1
+                    WHERE id = (SELECT id FROM product_images WHERE product_id = $2 ORDER BY position LIMIT 1)"#,
+                Utc::now().timestamp_millis(), product_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        self.remove_objects(&image, storage).await;
+        Ok(())
+    }
+}
+
+impl ProductImageServiceImpl {
+    async fn remove_objects(&self, image: &ProductImage, storage: &Storage) {
+        let mut keys = vec![image.storage_key.clone()];
+        keys.extend(image.thumbnails.keys().map(|size| thumbnail_key(image.product_id, image.id, *size)));
+
+        for key in keys {
+            if let Err(error) = storage.delete(&key).await {
+                tracing::warn!("failed to delete object {}: {}", key, error);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::RgbImage;
+
+    fn encode_png(width: u32, height: u32) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        RgbImage::new(width, height).write_to(&mut Cursor::new(&mut encoded), ImageFormat::Png).unwrap();
+        encoded
+    }
+
+    #[test]
+    fn process_image_builds_thumbnails() {
+        let processed = process_image(&encode_png(400, 200), &[100, 800]).unwrap();
+        assert_eq!(processed.format, ImageFormat::Png);
+        assert_eq!((processed.width, processed.height), (400, 200));
+        assert_eq!(processed.thumbnails.iter().map(|(size, _)| *size).collect::<Vec<_>>(), vec![100, 800]);
+    }
+
+    #[test]
+    fn process_image_rejects_oversized_dimensions() {
+        let result = process_image(&encode_png(MAX_IMAGE_DIMENSION + 1, 1), &[100]);
+        assert!(matches!(result, Err(Error::Protocol(_))));
+    }
+}
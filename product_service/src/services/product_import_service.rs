@@ -0,0 +1,409 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use bigdecimal::BigDecimal;
+use bytes::Bytes;
+use futures::{Stream, TryStreamExt};
+use serde_json::Value;
+use sqlx::{Error, PgConnection, Pool, Postgres};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+use common::validation::{FieldError, ValidationErrors};
+use crate::domain::product::{CreateProduct, UpdateProduct};
+use crate::domain::product_import::{ImportRowError, ProductExportRow, ProductFileFormat, ProductImportReport, ProductImportRow};
+use crate::services::error::ServiceError;
+use crate::services::product_service::{insert_product, is_valid_gtin, update_product, MAX_SKU_LENGTH};
+use crate::services::service::ProductImportService;
+
+const IMPORT_BATCH_SIZE: usize = 500;
+const EXPORT_BUFFER_SIZE: usize = 64;
+const EXPORT_COLUMNS: [&str; 10] = ["sku", "slug", "barcode", "name", "description", "price", "stock", "reorder_threshold", "category_id", "category"];
+
+pub struct ProductImportServiceImpl;
+
+// Nomor baris file beserta hasil parsing baris itu
+type ParsedRows = Vec<(u64, Result<ProductImportRow, String>)>;
+
+enum ImportAction {
+    Created,
+    Updated(Uuid),
+    Unchanged,
+}
+
+struct Categories {
+    archived_by_id: HashMap<Uuid, bool>,
+    active_by_name: HashMap<String, Vec<Uuid>>,
+}
+
+impl Categories {
+    async fn load(pool: &Pool<Postgres>) -> Result<Self, Error> {
+        let rows = sqlx::query!("SELECT id, name, deleted_at FROM categories")
+            .fetch_all(pool)
+            .await?;
+
+        let mut categories = Categories {
+            archived_by_id: HashMap::new(),
+            active_by_name: HashMap::new(),
+        };
+        for row in rows {
+            categories.archived_by_id.insert(row.id, row.deleted_at.is_some());
+            if row.deleted_at.is_none() {
+                categories.active_by_name.entry(row.name.to_lowercase()).or_default().push(row.id);
+            }
+        }
+        Ok(categories)
+    }
+
+    /// Kolom kategori boleh berisi id atau nama; nama dicocokkan tanpa membedakan huruf besar kecil.
+    fn resolve(&self, reference: &str) -> Result<Uuid, String> {
+        if let Ok(id) = Uuid::parse_str(reference) {
+            return match self.archived_by_id.get(&id) {
+                Some(false) => Ok(id),
+                Some(true) => Err(format!("category '{}' is archived", reference)),
+                None => Err(format!("category '{}' not found", reference)),
+            };
+        }
+
+        match self.active_by_name.get(&reference.to_lowercase()).map(Vec::as_slice) {
+            Some([id]) => Ok(*id),
+            Some([_, _, ..]) => Err(format!("category name '{}' is ambiguous, use the category id", reference)),
+            _ => Err(format!("category '{}' not found", reference)),
+        }
+    }
+}
+
+fn text(value: Option<String>) -> Option<String> {
+    value.map(|value| value.trim().to_string()).filter(|value| !value.is_empty())
+}
+
+fn parse_quantity(value: Option<String>, field: &str, errors: &mut ValidationErrors) -> Option<i32> {
+    let value = text(value)?;
+    match value.parse::<i32>() {
+        Ok(quantity) if quantity >= 0 => Some(quantity),
+        Ok(_) => {
+            errors.add(field, &format!("{} must not be negative", field));
+            None
+        }
+        Err(_) => {
+            errors.add(field, &format!("{} must be a whole number", field));
+            None
+        }
+    }
+}
+
+fn validate_row(row: ProductImportRow, line: u64, categories: &Categories, seen_skus: &mut HashMap<String, u64>) -> Result<CreateProduct, ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+
+    let sku = text(row.sku);
+    if let Some(sku) = &sku {
+        if sku.chars().count() > MAX_SKU_LENGTH {
+            errors.add("sku", &format!("sku must be at most {} characters", MAX_SKU_LENGTH));
+        } else if let Some(first_line) = seen_skus.insert(sku.clone(), line) {
+            errors.add("sku", &format!("sku is already used on line {}", first_line));
+        }
+    }
+
+    let barcode = text(row.barcode);
+    if barcode.as_deref().is_some_and(|barcode| !is_valid_gtin(barcode)) {
+        errors.add("barcode", "barcode must be a GTIN-8, GTIN-12, EAN-13 or GTIN-14 with a valid check digit");
+    }
+
+    let name = text(row.name);
+    if name.is_none() {
+        errors.add("name", "name is required");
+    }
+
+    let price = match text(row.price) {
+        None => {
+            errors.add("price", "price is required");
+            None
+        }
+        Some(price) => match BigDecimal::from_str(&price) {
+            Ok(price) if price >= 0 => Some(price),
+            Ok(_) => {
+                errors.add("price", "price must not be negative");
+                None
+            }
+            Err(_) => {
+                errors.add("price", "price must be a decimal number");
+                None
+            }
+        },
+    };
+
+    let stock = parse_quantity(row.stock, "stock", &mut errors);
+    let reorder_threshold = parse_quantity(row.reorder_threshold, "reorder_threshold", &mut errors);
+
+    // category_id didahulukan karena itu yang ditulis oleh export
+    let category_id = match (text(row.category_id), text(row.category)) {
+        (Some(reference), _) => categories.resolve(&reference)
+            .map_err(|message| errors.add("category_id", &message))
+            .ok(),
+        (None, Some(reference)) => categories.resolve(&reference)
+            .map_err(|message| errors.add("category", &message))
+            .ok(),
+        (None, None) => {
+            errors.add("category", "category or category_id is required");
+            None
+        }
+    };
+
+    match (name, price, category_id) {
+        (Some(name), Some(price), Some(category_id)) if errors.is_empty() => Ok(CreateProduct {
+            sku,
+            barcode,
+            name,
+            description: text(row.description),
+            price,
+            stock: stock.unwrap_or(0),
+            reorder_threshold,
+            category_id,
+        }),
+        _ => Err(errors),
+    }
+}
+
+fn parse_csv(data: &[u8]) -> Result<ParsedRows, Error> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(data);
+    let headers: csv::StringRecord = reader.headers()
+        .map_err(|error| Error::Protocol(format!("invalid CSV header: {}", error)))?
+        .iter()
+        .map(|header| header.to_lowercase())
+        .collect();
+
+    let mut rows = Vec::new();
+    let mut record = csv::StringRecord::new();
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(false) => break,
+            Ok(true) => {
+                let line = record.position().map(|position| position.line()).unwrap_or_default();
+                rows.push((line, record.deserialize(Some(&headers)).map_err(|error| error.to_string())));
+            }
+            Err(error) => {
+                let line = error.position().map(|position| position.line()).unwrap_or_default();
+                rows.push((line, Err(error.to_string())));
+            }
+        }
+    }
+    Ok(rows)
+}
+
+fn parse_ndjson(data: &[u8]) -> Result<ParsedRows, Error> {
+    let content = std::str::from_utf8(data)
+        .map_err(|error| Error::Protocol(format!("import file is not valid UTF-8: {}", error)))?;
+
+    Ok(content.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| (index as u64 + 1, parse_json_row(line)))
+        .collect())
+}
+
+fn parse_json_row(line: &str) -> Result<ProductImportRow, String> {
+    let object: serde_json::Map<String, Value> = serde_json::from_str(line).map_err(|error| error.to_string())?;
+    // Angka diubah ke teks supaya validasinya sama persis dengan baris CSV
+    let fields = object.into_iter()
+        .filter_map(|(key, value)| match value {
+            Value::Null => None,
+            Value::String(value) => Some((key, Value::String(value))),
+            value => Some((key, Value::String(value.to_string()))),
+        })
+        .collect();
+    serde_json::from_value(Value::Object(fields)).map_err(|error| error.to_string())
+}
+
+fn row_error(error: ServiceError) -> FieldError {
+    match error {
+        ServiceError::Database(Error::Database(error)) if error.is_unique_violation() => FieldError {
+            field: "sku".to_string(),
+            message: "sku already exists".to_string(),
+        },
+        ServiceError::Conflict(message) => FieldError {
+            field: "sku".to_string(),
+            message,
+        },
+        error => FieldError {
+            field: "row".to_string(),
+            message: error.to_string(),
+        },
+    }
+}
+
+/// Produk dengan SKU yang sudah ada diperbarui, sisanya dibuat baru. Stok dan kategori produk lama tidak
+/// diubah lewat import; stok tetap lewat stock adjustment supaya ledger konsisten.
+async fn upsert_product(conn: &mut PgConnection, product: CreateProduct, actor: &str) -> Result<ImportAction, ServiceError> {
+    let existing = match &product.sku {
+        Some(sku) => sqlx::query!("SELECT id, barcode, name, description, reorder_threshold, version FROM products WHERE sku = $1 AND deleted_at IS NULL", sku)
+            .fetch_optional(&mut *conn)
+            .await?,
+        None => None,
+    };
+    let Some(existing) = existing else {
+        // SKU produk yang diarsipkan tetap terpakai; import tidak ikut memulihkan produk itu
+        if let Some(sku) = &product.sku {
+            let archived = sqlx::query_scalar!(r#"SELECT EXISTS (SELECT 1 FROM products WHERE sku = $1 AND deleted_at IS NOT NULL) AS "exists!""#, sku)
+                .fetch_one(&mut *conn)
+                .await?;
+            if archived {
+                return Err(ServiceError::Conflict("sku belongs to an archived product, restore it before importing".to_string()));
+            }
+        }
+        insert_product(conn, product).await?;
+        return Ok(ImportAction::Created);
+    };
+
+    let request = UpdateProduct {
+        sku: None,
+        barcode: product.barcode.filter(|barcode| Some(barcode) != existing.barcode.as_ref()),
+        name: Some(product.name).filter(|name| *name != existing.name),
+        description: product.description.filter(|description| Some(description) != existing.description.as_ref()),
+        price: Some(product.price),
+        reorder_threshold: product.reorder_threshold.filter(|reorder_threshold| *reorder_threshold != existing.reorder_threshold),
+        version: None,
+    };
+    match update_product(conn, existing.id, request, existing.version, actor).await? {
+        true => Ok(ImportAction::Updated(existing.id)),
+        false => Ok(ImportAction::Unchanged),
+    }
+}
+
+fn encode_row(format: ProductFileFormat, row: &ProductExportRow) -> Result<Bytes, Error> {
+    match format {
+        ProductFileFormat::Csv => {
+            let mut writer = csv::WriterBuilder::new().has_headers(false).from_writer(Vec::new());
+            writer.serialize(row).map_err(|error| Error::Protocol(error.to_string()))?;
+            let encoded = writer.into_inner().map_err(|error| Error::Protocol(error.to_string()))?;
+            Ok(Bytes::from(encoded))
+        }
+        ProductFileFormat::Ndjson => {
+            let mut encoded = serde_json::to_vec(row).map_err(|error| Error::Protocol(error.to_string()))?;
+            encoded.push(b'\n');
+            Ok(Bytes::from(encoded))
+        }
+    }
+}
+
+async fn write_export(format: ProductFileFormat, include_deleted: bool, pool: &Pool<Postgres>, sender: &mpsc::Sender<Result<Bytes, Error>>) -> Result<(), Error> {
+    if format == ProductFileFormat::Csv {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(EXPORT_COLUMNS).map_err(|error| Error::Protocol(error.to_string()))?;
+        let header = writer.into_inner().map_err(|error| Error::Protocol(error.to_string()))?;
+        if sender.send(Ok(Bytes::from(header))).await.is_err() {
+            return Ok(());
+        }
+    }
+
+    let mut rows = sqlx::query_as!(ProductExportRow, r#"
+            SELECT products.sku, products.slug, products.barcode, products.name, products.description, products.price, products.stock, products.reorder_threshold,
+            products.category_id, categories.name AS category
+            FROM products INNER JOIN categories ON categories.id = products.category_id
+            WHERE $1 OR products.deleted_at IS NULL
+            ORDER BY products.created_at, products.id"#, include_deleted)
+        .fetch(pool);
+
+    while let Some(row) = rows.try_next().await? {
+        // Pengiriman gagal berarti client sudah memutus koneksi
+        if sender.send(Ok(encode_row(format, &row)?)).await.is_err() {
+            return Ok(());
+        }
+    }
+    Ok(())
+}
+
+impl ProductImportService for ProductImportServiceImpl {
+    async fn import(&self, data: Bytes, format: ProductFileFormat, dry_run: bool, actor: &str, pool: &Pool<Postgres>) -> Result<ProductImportReport, ServiceError> {
+        let rows = match format {
+            ProductFileFormat::Csv => parse_csv(&data)?,
+            ProductFileFormat::Ndjson => parse_ndjson(&data)?,
+        };
+        if rows.is_empty() {
+            return Err(Error::Protocol("import file contains no rows".to_string()).into());
+        }
+
+        let categories = Categories::load(pool).await?;
+        let mut report = ProductImportReport {
+            dry_run,
+            total: rows.len(),
+            ..ProductImportReport::default()
+        };
+
+        // Semua baris divalidasi dulu; satu baris saja yang salah berarti tidak ada yang disimpan
+        let mut seen_skus = HashMap::new();
+        let mut products = Vec::with_capacity(rows.len());
+        for (line, row) in rows {
+            let row = match row {
+                Ok(row) => row,
+                Err(message) => {
+                    report.errors.push(ImportRowError {
+                        line,
+                        sku: None,
+                        fields: vec![FieldError { field: "row".to_string(), message }],
+                    });
+                    continue;
+                }
+            };
+            let sku = text(row.sku.clone());
+            match validate_row(row, line, &categories, &mut seen_skus) {
+                Ok(product) => products.push((line, product)),
+                Err(errors) => report.errors.push(ImportRowError { line, sku, fields: errors.fields }),
+            }
+        }
+        if !report.errors.is_empty() {
+            return Ok(report);
+        }
+
+        let mut remaining = products.into_iter();
+        loop {
+            let batch: Vec<(u64, CreateProduct)> = remaining.by_ref().take(IMPORT_BATCH_SIZE).collect();
+            if batch.is_empty() {
+                break;
+            }
+
+            let mut tx = pool.begin().await?;
+            let (mut created, mut unchanged) = (0, 0);
+            let mut updated = Vec::new();
+            for (line, product) in batch {
+                let sku = product.sku.clone();
+                match upsert_product(&mut tx, product, actor).await {
+                    Ok(ImportAction::Created) => created += 1,
+                    Ok(ImportAction::Updated(id)) => updated.push(id),
+                    Ok(ImportAction::Unchanged) => unchanged += 1,
+                    Err(error) => {
+                        // Batch ini di-rollback saat tx di-drop, batch sebelumnya sudah tersimpan
+                        report.errors.push(ImportRowError { line, sku, fields: vec![row_error(error)] });
+                        return Ok(report);
+                    }
+                }
+            }
+
+            // Dry run tetap menjalankan semua query supaya konflik di database ikut terdeteksi
+            if dry_run {
+                tx.rollback().await?;
+            } else {
+                tx.commit().await?;
+                report.updated_ids.extend(&updated);
+            }
+            report.created += created;
+            report.updated += updated.len();
+            report.unchanged += unchanged;
+        }
+
+        Ok(report)
+    }
+
+    fn export(&self, format: ProductFileFormat, include_deleted: bool, pool: Pool<Postgres>) -> impl Stream<Item = Result<Bytes, Error>> + Send + 'static {
+        let (sender, receiver) = mpsc::channel(EXPORT_BUFFER_SIZE);
+        tokio::spawn(async move {
+            if let Err(error) = write_export(format, include_deleted, &pool, &sender).await {
+                tracing::warn!("product export failed: {}", error);
+                let _ = sender.send(Err(error)).await;
+            }
+        });
+
+        futures::stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|chunk| (chunk, receiver))
+        })
+    }
+}
\ No newline at end of file
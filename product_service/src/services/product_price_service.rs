@@ -0,0 +1,243 @@
+use std::time::Duration;
+use bigdecimal::BigDecimal;
+use bigdecimal::num_bigint::Sign;
+use sqlx::{Error, PgConnection, Pool, Postgres};
+use sqlx::types::chrono::Utc;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+use crate::domain::product_price::{CreateScheduledPrice, PriceStatus, ProductPrice};
+use crate::redis::cache::Cache;
+use crate::services::service::ProductPriceService;
+
+const SCHEDULER_INTERVAL: Duration = Duration::from_secs(60);
+
+pub struct ProductPriceServiceImpl;
+
+/// Menjalankan harga terjadwal yang sudah jatuh tempo dan mengembalikan harga normal
+/// setelah periode harga sementara selesai. Cache produk yang harganya berubah ikut dihapus.
+pub fn spawn_price_scheduler(pool: Pool<Postgres>, cache: Cache, mut shutdown: watch::Receiver<bool>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SCHEDULER_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown.changed() => break,
+            }
+            match ProductPriceServiceImpl.apply_scheduled_prices(&pool).await {
+                Ok(changed) if changed.is_empty() => {}
+                Ok(changed) => {
+                    cache.invalidate_products(&changed).await;
+                    tracing::info!("applied {} scheduled price changes", changed.len());
+                }
+                Err(error) => tracing::warn!("failed to apply scheduled prices: {}", error),
+            }
+        }
+    })
+}
+
+/// Mencatat perubahan harga yang langsung berlaku, dipanggil dalam transaksi yang sama dengan update produk.
+pub async fn record_price_change(
+    conn: &mut PgConnection,
+    product_id: Uuid,
+    price: &BigDecimal,
+    previous_price: Option<&BigDecimal>,
+    actor: &str,
+    effective_from: i64,
+) -> Result<(), Error> {
+    let now = Utc::now().timestamp_millis();
+    sqlx::query!(
+    "INSERT INTO product_prices (id, product_id, price, previous_price, effective_from, actor, status, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+        Uuid::new_v4(),
+        product_id,
+        price,
+        previous_price,
+        effective_from,
+        actor,
+        PriceStatus::Applied as _,
+        now,
+        now
+    ).execute(&mut *conn).await?;
+    Ok(())
+}
+
+async fn set_product_price(conn: &mut PgConnection, product_id: Uuid, price: &BigDecimal, now: i64) -> Result<BigDecimal, Error> {
+    let previous = sqlx::query_scalar!("SELECT price FROM products WHERE id = $1 FOR UPDATE", product_id)
+        .fetch_one(&mut *conn)
+        .await?;
+    sqlx::query!(
+        "UPDATE products SET price = $1, version = version + 1, updated_at = $2 WHERE id = $3",
+        price,
+        now,
+        product_id
+    )
+        .execute(&mut *conn)
+        .await?;
+    Ok(previous)
+}
+
+impl ProductPriceService for ProductPriceServiceImpl {
+    async fn fetch_price_history(&self, product_id: Uuid, pool: &Pool<Postgres>) -> Result<Vec<ProductPrice>, Error> {
+        let product_exists = sqlx::query_scalar!(r#"SELECT EXISTS (SELECT 1 FROM products WHERE id = $1) AS "exists!""#, product_id)
+            .fetch_one(pool)
+            .await?;
+        if !product_exists {
+            return Err(Error::RowNotFound);
+        }
+
+        let prices = sqlx::query_as!(ProductPrice, r#"
+                SELECT id, product_id, price, previous_price, effective_from, effective_until, actor,
+                status AS "status: PriceStatus", created_at, updated_at
+                FROM product_prices WHERE product_id = $1
+                ORDER BY effective_from DESC, created_at DESC"#, product_id)
+            .fetch_all(pool)
+            .await?;
+        Ok(prices)
+    }
+
+    async fn schedule_price(&self, product_id: Uuid, actor_id: Uuid, request: CreateScheduledPrice, pool: &Pool<Postgres>) -> Result<ProductPrice, Error> {
+        let now = Utc::now().timestamp_millis();
+        if request.price.sign() == Sign::Minus {
+            return Err(Error::Protocol("price must not be negative".to_string()));
+        }
+        if request.effective_from <= now {
+            return Err(Error::Protocol("effective_from must be in the future".to_string()));
+        }
+        if request.effective_until.is_some_and(|until| until <= request.effective_from) {
+            return Err(Error::Protocol("effective_until must be after effective_from".to_string()));
+        }
+
+        let archived = sqlx::query_scalar!(r#"SELECT deleted_at IS NOT NULL AS "archived!" FROM products WHERE id = $1"#, product_id)
+            .fetch_one(pool)
+            .await?;
+        if archived {
+            return Err(Error::Protocol("prices cannot be scheduled for an archived product".to_string()));
+        }
+
+        let price = ProductPrice {
+            id: Uuid::new_v4(),
+            product_id,
+            price: request.price,
+            previous_price: None,
+            effective_from: request.effective_from,
+            effective_until: request.effective_until,
+            actor: actor_id.to_string(),
+            status: PriceStatus::Scheduled,
+            created_at: now,
+            updated_at: now,
+        };
+
+        sqlx::query!(
+        "INSERT INTO product_prices (id, product_id, price, effective_from, effective_until, actor, status, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+            price.id,
+            price.product_id,
+            price.price,
+            price.effective_from,
+            price.effective_until,
+            price.actor,
+            price.status as _,
+            price.created_at,
+            price.updated_at
+        ).execute(pool).await?;
+
+        Ok(price)
+    }
+
+    async fn cancel_scheduled_price(&self, product_id: Uuid, id: Uuid, pool: &Pool<Postgres>) -> Result<(), Error> {
+        let cancel = sqlx::query!(
+            "UPDATE product_prices SET status = 'cancelled', updated_at = $1 WHERE id = $2 AND product_id = $3 AND status = 'scheduled'",
+            Utc::now().timestamp_millis(),
+            id,
+            product_id
+        )
+            .execute(pool)
+            .await;
+
+        match cancel.unwrap_or_default().rows_affected() {
+            1 => Ok(()),
+            _ => {
+                Err(Error::RowNotFound)
+            }
+        }
+    }
+
+    async fn apply_scheduled_prices(&self, pool: &Pool<Postgres>) -> Result<Vec<Uuid>, Error> {
+        let now = Utc::now().timestamp_millis();
+        let mut changed = Vec::new();
+
+        // Harga terjadwal yang sudah mulai berlaku, SKIP LOCKED supaya aman dijalankan di banyak instance
+        loop {
+            let mut tx = pool.begin().await?;
+            let due = sqlx::query!(r#"
+                    SELECT id, product_id, price, effective_until FROM product_prices
+                    WHERE status = 'scheduled' AND effective_from <= $1
+                    ORDER BY effective_from LIMIT 1 FOR UPDATE SKIP LOCKED"#, now)
+                .fetch_optional(&mut *tx)
+                .await?;
+            let Some(due) = due else { break };
+
+            if due.effective_until.is_some_and(|until| until <= now) {
+                // Periodenya sudah lewat sebelum sempat dijalankan
+                sqlx::query!("UPDATE product_prices SET status = 'ended', updated_at = $1 WHERE id = $2", now, due.id)
+                    .execute(&mut *tx)
+                    .await?;
+            } else {
+                let previous = set_product_price(&mut tx, due.product_id, &due.price, now).await?;
+                sqlx::query!(
+                    "UPDATE product_prices SET status = 'applied', previous_price = $1, updated_at = $2 WHERE id = $3",
+                    previous,
+                    now,
+                    due.id
+                )
+                    .execute(&mut *tx)
+                    .await?;
+                changed.push(due.product_id);
+            }
+            tx.commit().await?;
+        }
+
+        // Harga sementara yang periodenya selesai dikembalikan ke harga yang masih berlaku saat itu: promo lain yang
+        // lebih luas kalau periodenya belum selesai (misalnya flash sale di dalam promo akhir pekan), atau harga normal
+        loop {
+            let mut tx = pool.begin().await?;
+            let ending = sqlx::query!(r#"
+                    SELECT id, product_id, previous_price, effective_until AS "effective_until!" FROM product_prices
+                    WHERE status = 'applied' AND effective_until IS NOT NULL AND effective_until <= $1
+                    ORDER BY effective_until LIMIT 1 FOR UPDATE SKIP LOCKED"#, now)
+                .fetch_optional(&mut *tx)
+                .await?;
+            let Some(ending) = ending else { break };
+
+            let in_effect = sqlx::query!(r#"
+                    SELECT price, effective_until FROM product_prices
+                    WHERE product_id = $1 AND id <> $2 AND status = 'applied' AND effective_from <= $3
+                    AND (effective_until IS NULL OR effective_until > $3)
+                    ORDER BY effective_from DESC, created_at DESC LIMIT 1"#, ending.product_id, ending.id, ending.effective_until)
+                .fetch_optional(&mut *tx)
+                .await?;
+
+            sqlx::query!("UPDATE product_prices SET status = 'ended', updated_at = $1 WHERE id = $2", now, ending.id)
+                .execute(&mut *tx)
+                .await?;
+
+            let (restored_price, temporary) = match in_effect {
+                Some(row) => (Some(row.price), row.effective_until.is_some()),
+                None => (ending.previous_price, false),
+            };
+            if let Some(restored_price) = restored_price {
+                let previous = set_product_price(&mut tx, ending.product_id, &restored_price, now).await?;
+                // Promo yang masih berjalan sudah punya baris riwayat sendiri; baris baru tanpa effective_until
+                // akan dianggap harga normal saat promo itu selesai nanti
+                if previous != restored_price && !temporary {
+                    record_price_change(&mut tx, ending.product_id, &restored_price, Some(&previous), "scheduler", ending.effective_until).await?;
+                }
+                if previous != restored_price {
+                    changed.push(ending.product_id);
+                }
+            }
+            tx.commit().await?;
+        }
+
+        Ok(changed)
+    }
+}
\ No newline at end of file
@@ -1,38 +1,792 @@
-use sqlx::{Error, Pool, Postgres};
+use std::collections::{BTreeMap, HashMap};
+use bytes::Bytes;
+use image::ImageFormat;
+use sqlx::{Error, PgConnection, Pool, Postgres, QueryBuilder};
 use sqlx::types::chrono::Utc;
+use sqlx::types::Json;
 use uuid::Uuid;
-use crate::domain::product::ProductReview;
-use crate::services::product_service;
+use common::pagination::{decode_cursor, encode_cursor};
+use common::validation::ValidationErrors;
+use crate::domain::product::{CreateProductReview, ModerationCursor, ModerationQuery, ProductReview, RatingSummary, RejectProductReview, ReportProductReview, ReviewCursor, ReviewPage, ReviewQuery, ReviewReport, ReviewSort, ReviewResponse, ReviewStatus, SaveReviewResponse, UpdateProductReview, VoteProductReview};
+use crate::domain::product_image::ReviewImage;
+use crate::services::error::ServiceError;
+use crate::orders::{OrderClient, Orders};
+use crate::services::{category_service, product_service};
+use crate::services::product_image_service::process_image;
 use crate::services::service::{ProductReviewService, ProductService};
+use crate::storage::{ObjectStorage, Storage};
+
+const DEFAULT_PAGE_SIZE: i64 = 20;
+const MAX_PAGE_SIZE: i64 = 100;
+const MAX_COMMENT_LENGTH: usize = 2000;
+const MAX_REASON_LENGTH: usize = 500;
+const REVIEW_COLUMNS: &str = "id, product_id, user_id, comment, rating, helpful_count, unhelpful_count, verified_purchase, status, moderation_reason, created_at, updated_at";
+const MAX_REVIEW_IMAGES: i64 = 5;
+const REVIEW_THUMBNAIL_SIZES: [u32; 1] = [300];
+const BLOCKED_WORDS: [&str; 8] = ["asshole", "bangsat", "bastard", "bitch", "fuck", "kontol", "memek", "shit"];
 
 pub struct ProductReviewServiceImpl;
 
+// Semua urutan dibuat DESC supaya keyset cukup satu perbandingan tuple.
+// Ulasan tanpa rating selalu di akhir: dianggap 0 untuk Highest dan 6 (di atas rating maksimum) untuk Lowest.
+fn sort_key(sort: ReviewSort) -> &'static str {
+    match sort {
+        ReviewSort::Newest => "created_at",
+        ReviewSort::Highest => "COALESCE(rating, 0)::bigint",
+        ReviewSort::Lowest => "-COALESCE(rating, 6)::bigint",
+        ReviewSort::MostHelpful => "helpful_count::bigint",
+    }
+}
+
+fn review_cursor(review: &ProductReview, sort: ReviewSort) -> ReviewCursor {
+    let created_at = review.created_at.unwrap_or_default();
+    let value = match sort {
+        ReviewSort::Newest => created_at,
+        ReviewSort::Highest => review.rating.unwrap_or(0) as i64,
+        ReviewSort::Lowest => -(review.rating.unwrap_or(6) as i64),
+        ReviewSort::MostHelpful => review.helpful_count as i64,
+    };
+    ReviewCursor { sort, value, created_at, id: review.id.unwrap_or_default() }
+}
+
+fn validate_review(comment: Option<&str>, rating: Option<i32>) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+
+    if let Some(rating) = rating {
+        if !(1..=5).contains(&rating) {
+            errors.add("rating", "must be between 1 and 5");
+        }
+    }
+    if let Some(comment) = comment {
+        if comment.trim().is_empty() {
+            errors.add("comment", "must not be blank");
+        } else if comment.chars().count() > MAX_COMMENT_LENGTH {
+            errors.add("comment", &format!("must be at most {} characters", MAX_COMMENT_LENGTH));
+        } else if contains_blocked_word(comment) {
+            errors.add("comment", "contains inappropriate language");
+        }
+    }
+
+    errors.into_result()
+}
+
+fn validate_reason(reason: &str) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    if reason.trim().is_empty() {
+        errors.add("reason", "must not be blank");
+    } else if reason.chars().count() > MAX_REASON_LENGTH {
+        errors.add("reason", &format!("must be at most {} characters", MAX_REASON_LENGTH));
+    }
+    errors.into_result()
+}
+
+fn contains_blocked_word(text: &str) -> bool {
+    text.split(|c: char| !c.is_alphanumeric())
+        .any(|word| BLOCKED_WORDS.iter().any(|blocked| word.eq_ignore_ascii_case(blocked)))
+}
+
+fn review_image_key(review_id: Uuid, image_id: Uuid, format: ImageFormat) -> String {
+    let extension = format.extensions_str().first().copied().unwrap_or("bin");
+    format!("reviews/{}/{}/original.{}", review_id, image_id, extension)
+}
+
+fn review_thumbnail_key(review_id: Uuid, image_id: Uuid, size: u32) -> String {
+    format!("reviews/{}/{}/thumb_{}.jpg", review_id, image_id, size)
+}
+
+async fn remove_image_objects(image: &ReviewImage, storage: &Storage) {
+    let mut keys = vec![image.storage_key.clone()];
+    keys.extend(image.thumbnails.keys().map(|size| review_thumbnail_key(image.review_id, image.id, *size)));
+
+    for key in keys {
+        if let Err(error) = storage.delete(&key).await {
+            tracing::warn!("failed to delete object {}: {}", key, error);
+        }
+    }
+}
+
+pub async fn fetch_review_images(review_ids: &[Uuid], pool: &Pool<Postgres>) -> Result<HashMap<Uuid, Vec<ReviewImage>>, Error> {
+    let images = sqlx::query_as!(ReviewImage, r#"
+            SELECT id, review_id, storage_key, url, thumbnails AS "thumbnails: Json<BTreeMap<u32, String>>", content_type,
+            width, height, position, created_at
+            FROM review_images WHERE review_id = ANY($1) ORDER BY position"#, review_ids)
+        .fetch_all(pool)
+        .await?;
+
+    let mut images_by_review: HashMap<Uuid, Vec<ReviewImage>> = HashMap::new();
+    for image in images {
+        images_by_review.entry(image.review_id).or_default().push(image);
+    }
+    Ok(images_by_review)
+}
+
+pub async fn fetch_review_responses(review_ids: &[Uuid], pool: &Pool<Postgres>) -> Result<HashMap<Uuid, ReviewResponse>, Error> {
+    let responses = sqlx::query_as!(ReviewResponse,
+            "SELECT review_id, responder_id, body, created_at, updated_at FROM review_responses WHERE review_id = ANY($1)", review_ids)
+        .fetch_all(pool)
+        .await?;
+    Ok(responses.into_iter().map(|response| (response.review_id, response)).collect())
+}
+
+async fn attach_details(reviews: &mut [ProductReview], pool: &Pool<Postgres>) -> Result<(), Error> {
+    let review_ids: Vec<Uuid> = reviews.iter().filter_map(|review| review.id).collect();
+    let mut images_by_review = fetch_review_images(&review_ids, pool).await?;
+    let mut responses = fetch_review_responses(&review_ids, pool).await?;
+    for review in reviews {
+        if let Some(id) = review.id {
+            review.images = images_by_review.remove(&id).unwrap_or_default();
+            review.response = responses.remove(&id);
+        }
+    }
+    Ok(())
+}
+
+fn validate_response(body: &str) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    if body.trim().is_empty() {
+        errors.add("body", "must not be blank");
+    } else if body.chars().count() > MAX_COMMENT_LENGTH {
+        errors.add("body", &format!("must be at most {} characters", MAX_COMMENT_LENGTH));
+    } else if contains_blocked_word(body) {
+        errors.add("body", "contains inappropriate language");
+    }
+    errors.into_result()
+}
+
+pub async fn fetch_rating_summary(product_id: Uuid, pool: &Pool<Postgres>) -> Result<RatingSummary, Error> {
+    let summary = sqlx::query_as!(RatingSummary, r#"
+            SELECT review_count, rating_average AS average_rating,
+            ARRAY[rating_1, rating_2, rating_3, rating_4, rating_5] AS "rating_histogram!"
+            FROM product_ratings WHERE product_id = $1"#, product_id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(summary.unwrap_or_default())
+}
+
+/// Memperbarui agregat rating secara inkremental dalam transaksi yang sama dengan perubahan ulasan.
+/// Upsert mengunci baris agregat sehingga perubahan paralel tetap konsisten.
+async fn apply_rating_change(conn: &mut PgConnection, product_id: Uuid, review_delta: i32, removed: Option<i32>, added: Option<i32>) -> Result<(), Error> {
+    let mut histogram = [0i32; 5];
+    let mut rating_sum = 0;
+    if let Some(rating) = removed.filter(|rating| (1..=5).contains(rating)) {
+        histogram[rating as usize - 1] -= 1;
+        rating_sum -= rating;
+    }
+    if let Some(rating) = added.filter(|rating| (1..=5).contains(rating)) {
+        histogram[rating as usize - 1] += 1;
+        rating_sum += rating;
+    }
+
+    sqlx::query!(r#"
+            INSERT INTO product_ratings (product_id, review_count, rating_sum, rating_1, rating_2, rating_3, rating_4, rating_5, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (product_id) DO UPDATE SET
+                review_count = product_ratings.review_count + EXCLUDED.review_count,
+                rating_sum = product_ratings.rating_sum + EXCLUDED.rating_sum,
+                rating_1 = product_ratings.rating_1 + EXCLUDED.rating_1,
+                rating_2 = product_ratings.rating_2 + EXCLUDED.rating_2,
+                rating_3 = product_ratings.rating_3 + EXCLUDED.rating_3,
+                rating_4 = product_ratings.rating_4 + EXCLUDED.rating_4,
+                rating_5 = product_ratings.rating_5 + EXCLUDED.rating_5,
+                updated_at = EXCLUDED.updated_at"#,
+        product_id,
+        review_delta,
+        rating_sum,
+        histogram[0],
+        histogram[1],
+        histogram[2],
+        histogram[3],
+        histogram[4],
+        Utc::now().timestamp_millis()
+    )
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+// Agregat rating hanya menghitung ulasan yang sudah disetujui
+async fn apply_status_change(conn: &mut PgConnection, product_id: Uuid, rating: Option<i32>, from: ReviewStatus, to: ReviewStatus) -> Result<(), Error> {
+    match (from == ReviewStatus::Approved, to == ReviewStatus::Approved) {
+        (true, false) => apply_rating_change(conn, product_id, -1, rating, None).await,
+        (false, true) => apply_rating_change(conn, product_id, 1, None, rating).await,
+        _ => Ok(()),
+    }
+}
+
+impl ProductReviewServiceImpl {
+    async fn fetch_own_review(&self, product_id: Uuid, id: Uuid, user_id: Uuid, pool: &Pool<Postgres>) -> Result<ProductReview, ServiceError> {
+        let review = sqlx::query_as::<_, ProductReview>(&format!("SELECT {} FROM product_reviews WHERE id = $1 AND product_id = $2", REVIEW_COLUMNS))
+            .bind(id)
+            .bind(product_id)
+            .fetch_one(pool)
+            .await?;
+
+        if review.user_id != Some(user_id) {
+            return Err(ServiceError::Forbidden("only the author can modify this review".to_string()));
+        }
+        Ok(review)
+    }
+
+    async fn moderate(&self, product_id: Uuid, id: Uuid, status: ReviewStatus, moderator: Uuid, reason: Option<String>, pool: &Pool<Postgres>) -> Result<ProductReview, ServiceError> {
+        let mut tx = pool.begin().await?;
+        let current = sqlx::query!(r#"SELECT rating, status AS "status: ReviewStatus" FROM product_reviews WHERE id = $1 AND product_id = $2 FOR UPDATE"#, id, product_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        let review = sqlx::query_as::<_, ProductReview>(&format!(
+            "UPDATE product_reviews SET status = $1, moderation_reason = $2, moderated_by = $3, moderated_at = $4 WHERE id = $5 RETURNING {}", REVIEW_COLUMNS))
+            .bind(status)
+            .bind(reason)
+            .bind(moderator)
+            .bind(Utc::now().timestamp_millis())
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+        apply_status_change(&mut tx, product_id, current.rating, current.status, status).await?;
+
+        tx.commit().await?;
+        self.with_details(review, pool).await
+    }
+
+    async fn with_details(&self, mut review: ProductReview, pool: &Pool<Postgres>) -> Result<ProductReview, ServiceError> {
+        attach_details(std::slice::from_mut(&mut review), pool).await?;
+        Ok(review)
+    }
+
+    async fn apply_vote(&self, product_id: Uuid, id: Uuid, user_id: Uuid, helpful: Option<bool>, pool: &Pool<Postgres>) -> Result<ProductReview, ServiceError> {
+        let mut tx = pool.begin().await?;
+        // Baris ulasan dikunci supaya penghitung suara tetap sinkron dengan tabel review_votes
+        let review = sqlx::query!(r#"SELECT user_id, status AS "status: ReviewStatus" FROM product_reviews WHERE id = $1 AND product_id = $2 FOR UPDATE"#, id, product_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .filter(|review| review.status == ReviewStatus::Approved)
+            .ok_or(Error::RowNotFound)?;
+        if review.user_id == Some(user_id) {
+            return Err(ServiceError::Forbidden("authors cannot vote on their own review".to_string()));
+        }
+
+        let previous = sqlx::query_scalar!("SELECT helpful FROM review_votes WHERE review_id = $1 AND user_id = $2", id, user_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        let now = Utc::now().timestamp_millis();
+        match helpful {
+            Some(helpful) => {
+                sqlx::query!(r#"
+                        INSERT INTO review_votes (review_id, user_id, helpful, created_at, updated_at) VALUES ($1, $2, $3, $4, $4)
+                        ON CONFLICT (review_id, user_id) DO UPDATE SET helpful = EXCLUDED.helpful, updated_at = EXCLUDED.updated_at"#,
+                    id, user_id, helpful, now)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            None => {
+                sqlx::query!("DELETE FROM review_votes WHERE review_id = $1 AND user_id = $2", id, user_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+
+        let helpful_delta = (helpful == Some(true)) as i32 - (previous == Some(true)) as i32;
+        let unhelpful_delta = (helpful == Some(false)) as i32 - (previous == Some(false)) as i32;
+        let review = sqlx::query_as::<_, ProductReview>(&format!(
+            "UPDATE product_reviews SET helpful_count = helpful_count + $1, unhelpful_count = unhelpful_count + $2 WHERE id = $3 RETURNING {}", REVIEW_COLUMNS))
+            .bind(helpful_delta)
+            .bind(unhelpful_delta)
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        self.with_details(review, pool).await
+    }
+}
+
 impl ProductReviewService for ProductReviewServiceImpl {
-    async fn save(&self, product_id: Uuid, user_id: Uuid, comment: Option<String>, rating: Option<i32>, pool: &Pool<Postgres>) -> Result<ProductReview, Error> {
+    async fn fetch_reviews(&self, product_id: Uuid, query: ReviewQuery, pool: &Pool<Postgres>) -> Result<ReviewPage, Error> {
+        let sort = query.sort.unwrap_or_default();
+        let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        let cursor = match &query.cursor {
+            Some(cursor) => Some(decode_cursor::<ReviewCursor>(cursor)
+                .filter(|cursor| cursor.sort == sort)
+                .ok_or_else(|| Error::Protocol("invalid cursor".to_string()))?),
+            None => None,
+        };
+
+        let product_exists = sqlx::query_scalar!(r#"SELECT EXISTS (SELECT 1 FROM products WHERE id = $1) AS "exists!""#, product_id)
+            .fetch_one(pool)
+            .await?;
+        if !product_exists {
+            return Err(Error::RowNotFound);
+        }
+
+        let mut query_builder = QueryBuilder::<Postgres>::new(format!("SELECT {} FROM product_reviews WHERE status = 'approved' AND product_id = ", REVIEW_COLUMNS));
+        query_builder.push_bind(product_id);
+        if let Some(cursor) = &cursor {
+            query_builder.push(format!(" AND ({}, created_at, id) < (", sort_key(sort)));
+            query_builder.push_bind(cursor.value);
+            query_builder.push(", ");
+            query_builder.push_bind(cursor.created_at);
+            query_builder.push(", ");
+            query_builder.push_bind(cursor.id);
+            query_builder.push(")");
+        }
+        query_builder.push(format!(" ORDER BY {} DESC, created_at DESC, id DESC LIMIT ", sort_key(sort)));
+        query_builder.push_bind(limit + 1);
+
+        let mut reviews = query_builder.build_query_as::<ProductReview>()
+            .fetch_all(pool)
+            .await?;
+
+        let has_more = reviews.len() as i64 > limit;
+        reviews.truncate(limit as usize);
+        let next_cursor = reviews.last()
+            .filter(|_| has_more)
+            .map(|review| encode_cursor(&review_cursor(review, sort)));
+        attach_details(&mut reviews, pool).await?;
+
+        let total = sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM product_reviews WHERE status = 'approved' AND product_id = $1"#, product_id)
+            .fetch_one(pool)
+            .await?;
+
+        Ok(ReviewPage {
+            reviews,
+            next_cursor,
+            total,
+        })
+    }
+
+    async fn save(&self, product_id: Uuid, user_id: Uuid, request: CreateProductReview, order_client: &Orders, pool: &Pool<Postgres>) -> Result<ProductReview, ServiceError> {
+        let mut errors = validate_review(request.comment.as_deref(), request.rating).err().unwrap_or_default();
+        if request.comment.is_none() && request.rating.is_none() {
+            errors.add("rating", "either a rating or a comment is required");
+        }
+        errors.into_result()?;
+
         let product = product_service::ProductServiceImpl.fetch_by_id(product_id, pool).await?;
+        let category_id = sqlx::query_scalar!("SELECT category_id FROM products WHERE id = $1", product.id)
+            .fetch_one(pool)
+            .await?;
+        let verification_required = category_service::requires_verified_purchase(category_id, pool).await?;
+
+        // Kalau order service tidak bisa dihubungi, ulasan tetap diterima tanpa lencana kecuali kategorinya mewajibkan
+        let verified_purchase = match order_client.has_delivered_order(user_id, product.id).await {
+            Ok(verified_purchase) => verified_purchase,
+            Err(error) if !verification_required => {
+                tracing::warn!("failed to verify purchase for review of product {}: {}", product.id, error);
+                false
+            }
+            Err(error) => {
+                return Err(ServiceError::Unavailable(format!("unable to verify purchase: {}", error)));
+            }
+        };
+        if verification_required && !verified_purchase {
+            return Err(ServiceError::Forbidden("only customers with a delivered order can review products in this category".to_string()));
+        }
 
         let now = Utc::now().timestamp_millis();
         let product_review = ProductReview {
             id: Some(Uuid::new_v4()),
             product_id: Some(product.id),
             user_id: Some(user_id),
-            comment,
-            rating,
+            comment: request.comment,
+            rating: request.rating,
+            helpful_count: 0,
+            unhelpful_count: 0,
+            images: Vec::new(),
+            response: None,
+            verified_purchase,
+            status: ReviewStatus::Pending,
+            moderation_reason: None,
             created_at: Some(now),
             updated_at: Some(now),
         };
 
-        sqlx::query!(
-        "INSERT INTO product_reviews (id, user_id, comment, rating, product_id, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        // Ulasan baru masuk antrean moderasi dan belum dihitung di agregat rating
+        let inserted = sqlx::query!(
+        "INSERT INTO product_reviews (id, user_id, comment, rating, product_id, verified_purchase, status, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
             product_review.id,
             product_review.user_id,
             product_review.comment,
             product_review.rating,
             product_review.product_id,
+            product_review.verified_purchase,
+            product_review.status as _,
             product_review.created_at,
             product_review.updated_at
-        ).execute(pool).await?;
+        ).execute(pool).await;
+
+        // Satu ulasan per user per produk dijaga oleh unique constraint di database
+        match inserted {
+            Err(Error::Database(error)) if error.is_unique_violation() => {
+                return Err(ServiceError::Conflict("user has already reviewed this product".to_string()));
+            }
+            result => {
+                result?;
+            }
+        }
 
         Ok(product_review)
     }
+
+    async fn update(&self, product_id: Uuid, id: Uuid, user_id: Uuid, request: UpdateProductReview, pool: &Pool<Postgres>) -> Result<ProductReview, ServiceError> {
+        validate_review(request.comment.as_deref(), request.rating)?;
+
+        let mut review = self.fetch_own_review(product_id, id, user_id, pool).await?;
+
+        if let Some(comment) = request.comment {
+            review.comment = Some(comment);
+        }
+        if let Some(rating) = request.rating {
+            review.rating = Some(rating);
+        }
+        review.updated_at = Some(Utc::now().timestamp_millis());
+        // Ulasan yang diubah harus dimoderasi ulang
+        review.status = ReviewStatus::Pending;
+        review.moderation_reason = None;
+
+        // Melakukan update di database, status lama dikunci supaya agregat dihitung dari nilai yang benar
+        let mut tx = pool.begin().await?;
+        let previous = sqlx::query!(r#"SELECT rating, status AS "status: ReviewStatus" FROM product_reviews WHERE id = $1 FOR UPDATE"#, id)
+            .fetch_one(&mut *tx)
+            .await?;
+        sqlx::query!(
+            "UPDATE product_reviews SET comment = $1, rating = $2, updated_at = $3, status = $4, moderation_reason = NULL, moderated_by = NULL, moderated_at = NULL WHERE id = $5",
+            review.comment,
+            review.rating,
+            review.updated_at,
+            review.status as _,
+            id
+        )
+            .execute(&mut *tx)
+            .await?;
+        apply_status_change(&mut tx, product_id, previous.rating, previous.status, review.status).await?;
+
+        tx.commit().await?;
+        self.with_details(review, pool).await
+    }
+
+    async fn delete(&self, product_id: Uuid, id: Uuid, user_id: Uuid, storage: &Storage, pool: &Pool<Postgres>) -> Result<(), ServiceError> {
+        self.fetch_own_review(product_id, id, user_id, pool).await?;
+        let images = fetch_review_images(&[id], pool).await?.remove(&id).unwrap_or_default();
+
+        let mut tx = pool.begin().await?;
+        let deleted = sqlx::query!(r#"DELETE FROM product_reviews WHERE id = $1 AND user_id = $2 RETURNING rating, status AS "status: ReviewStatus""#, id, user_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        match deleted {
+            Some(review) => {
+                apply_status_change(&mut tx, product_id, review.rating, review.status, ReviewStatus::Rejected).await?;
+                tx.commit().await?;
+                for image in &images {
+                    remove_image_objects(image, storage).await;
+                }
+                Ok(())
+            }
+            None => {
+                Err(Error::RowNotFound.into())
+            }
+        }
+    }
+
+    async fn fetch_moderation_queue(&self, query: ModerationQuery, pool: &Pool<Postgres>) -> Result<ReviewPage, Error> {
+        let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        let cursor = match &query.cursor {
+            Some(cursor) => Some(decode_cursor::<ModerationCursor>(cursor)
+                .ok_or_else(|| Error::Protocol("invalid cursor".to_string()))?),
+            None => None,
+        };
+
+        // Antrean diproses dari ulasan terlama
+        let mut query_builder = QueryBuilder::<Postgres>::new(format!("SELECT {} FROM product_reviews WHERE ", REVIEW_COLUMNS));
+        let mut count_builder = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM product_reviews WHERE ");
+        for builder in [&mut query_builder, &mut count_builder] {
+            match query.status {
+                Some(status) => {
+                    builder.push("status = ");
+                    builder.push_bind(status);
+                }
+                None => {
+                    builder.push("status IN ('pending', 'flagged')");
+                }
+            }
+        }
+        if let Some(cursor) = &cursor {
+            query_builder.push(" AND (created_at, id) > (");
+            query_builder.push_bind(cursor.created_at);
+            query_builder.push(", ");
+            query_builder.push_bind(cursor.id);
+            query_builder.push(")");
+        }
+        query_builder.push(" ORDER BY created_at, id LIMIT ");
+        query_builder.push_bind(limit + 1);
+
+        let mut reviews = query_builder.build_query_as::<ProductReview>()
+            .fetch_all(pool)
+            .await?;
+
+        let has_more = reviews.len() as i64 > limit;
+        reviews.truncate(limit as usize);
+        let next_cursor = reviews.last()
+            .filter(|_| has_more)
+            .map(|review| encode_cursor(&ModerationCursor {
+                created_at: review.created_at.unwrap_or_default(),
+                id: review.id.unwrap_or_default(),
+            }));
+        attach_details(&mut reviews, pool).await?;
+
+        let total = count_builder.build_query_scalar::<i64>()
+            .fetch_one(pool)
+            .await?;
+
+        Ok(ReviewPage {
+            reviews,
+            next_cursor,
+            total,
+        })
+    }
+
+    async fn approve(&self, product_id: Uuid, id: Uuid, moderator: Uuid, pool: &Pool<Postgres>) -> Result<ProductReview, ServiceError> {
+        self.moderate(product_id, id, ReviewStatus::Approved, moderator, None, pool).await
+    }
+
+    async fn reject(&self, product_id: Uuid, id: Uuid, moderator: Uuid, request: RejectProductReview, pool: &Pool<Postgres>) -> Result<ProductReview, ServiceError> {
+        validate_reason(&request.reason)?;
+        self.moderate(product_id, id, ReviewStatus::Rejected, moderator, Some(request.reason), pool).await
+    }
+
+    async fn report(&self, product_id: Uuid, id: Uuid, user_id: Uuid, request: ReportProductReview, pool: &Pool<Postgres>) -> Result<ReviewReport, ServiceError> {
+        validate_reason(&request.reason)?;
+
+        let mut tx = pool.begin().await?;
+        let current = sqlx::query!(r#"SELECT rating, status AS "status: ReviewStatus" FROM product_reviews WHERE id = $1 AND product_id = $2 FOR UPDATE"#, id, product_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .filter(|review| matches!(review.status, ReviewStatus::Approved | ReviewStatus::Flagged))
+            .ok_or(Error::RowNotFound)?;
+
+        let report = ReviewReport {
+            id: Uuid::new_v4(),
+            review_id: id,
+            user_id,
+            reason: request.reason,
+            created_at: Utc::now().timestamp_millis(),
+        };
+        let inserted = sqlx::query!(
+            "INSERT INTO review_reports (id, review_id, user_id, reason, created_at) VALUES ($1, $2, $3, $4, $5)",
+            report.id,
+            report.review_id,
+            report.user_id,
+            report.reason,
+            report.created_at
+        ).execute(&mut *tx).await;
+        match inserted {
+            Err(Error::Database(error)) if error.is_unique_violation() => {
+                return Err(ServiceError::Conflict("user has already reported this review".to_string()));
+            }
+            result => {
+                result?;
+            }
+        }
+
+        // Ulasan yang dilaporkan disembunyikan sampai admin memeriksanya
+        if current.status == ReviewStatus::Approved {
+            sqlx::query!("UPDATE product_reviews SET status = 'flagged' WHERE id = $1", id)
+                .execute(&mut *tx)
+                .await?;
+            apply_status_change(&mut tx, product_id, current.rating, current.status, ReviewStatus::Flagged).await?;
+        }
+
+        tx.commit().await?;
+        Ok(report)
+    }
+    async fn vote(&self, product_id: Uuid, id: Uuid, user_id: Uuid, request: VoteProductReview, pool: &Pool<Postgres>) -> Result<ProductReview, ServiceError> {
+        self.apply_vote(product_id, id, user_id, Some(request.helpful), pool).await
+    }
+
+    async fn remove_vote(&self, product_id: Uuid, id: Uuid, user_id: Uuid, pool: &Pool<Postgres>) -> Result<ProductReview, ServiceError> {
+        self.apply_vote(product_id, id, user_id, None, pool).await
+    }
+
+    async fn save_image(&self, product_id: Uuid, id: Uuid, user_id: Uuid, data: Bytes, storage: &Storage, pool: &Pool<Postgres>) -> Result<ReviewImage, ServiceError> {
+        self.fetch_own_review(product_id, id, user_id, pool).await?;
+        let image_count = sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM review_images WHERE review_id = $1"#, id)
+            .fetch_one(pool)
+            .await?;
+        if image_count >= MAX_REVIEW_IMAGES {
+            let mut errors = ValidationErrors::new();
+            errors.add("file", &format!("a review can have at most {} images", MAX_REVIEW_IMAGES));
+            return Err(errors.into());
+        }
+
+        // Decode dan resize memakan CPU, jadi jangan jalankan di thread async
+        let source = data.clone();
+        let processed = tokio::task::spawn_blocking(move || process_image(&source, &REVIEW_THUMBNAIL_SIZES))
+            .await
+            .map_err(|error| Error::Protocol(error.to_string()))??;
+
+        let image_id = Uuid::new_v4();
+        let storage_key = review_image_key(id, image_id, processed.format);
+        storage.put(&storage_key, data, processed.format.to_mime_type()).await.map_err(Error::Io)?;
+
+        let mut thumbnails = BTreeMap::new();
+        for (size, encoded) in processed.thumbnails {
+            let key = review_thumbnail_key(id, image_id, size);
+            storage.put(&key, Bytes::from(encoded), ImageFormat::Jpeg.to_mime_type()).await.map_err(Error::Io)?;
+            thumbnails.insert(size, storage.url(&key));
+        }
+
+        let mut image = ReviewImage {
+            id: image_id,
+            review_id: id,
+            url: storage.url(&storage_key),
+            storage_key,
+            thumbnails: Json(thumbnails),
+            content_type: processed.format.to_mime_type().to_string(),
+            width: processed.width as i32,
+            height: processed.height as i32,
+            position: 0,
+            created_at: Utc::now().timestamp_millis(),
+        };
+
+        // Foto baru ikut dimoderasi, jadi ulasan kembali ke antrean seperti saat diubah
+        let inserted: Result<i32, ServiceError> = async {
+            let mut tx = pool.begin().await?;
+            let review = sqlx::query!(r#"SELECT rating, status AS "status: ReviewStatus" FROM product_reviews WHERE id = $1 FOR UPDATE"#, id)
+                .fetch_one(&mut *tx)
+                .await?;
+            let position = sqlx::query_scalar!(r#"
+                    INSERT INTO review_images (id, review_id, storage_key, url, thumbnails, content_type, width, height, position, created_at)
+                    SELECT $1, $2, $3, $4, $5, $6, $7, $8, COALESCE((SELECT MAX(position) + 1 FROM review_images WHERE review_id = $2), 0), $9
+                    WHERE (SELECT COUNT(*) FROM review_images WHERE review_id = $2) < $10
+                    RETURNING position"#,
+                image.id,
+                image.review_id,
+                image.storage_key,
+                image.url,
+                &image.thumbnails as _,
+                image.content_type,
+                image.width,
+                image.height,
+                image.created_at,
+                MAX_REVIEW_IMAGES
+            )
+                .fetch_optional(&mut *tx)
+                .await?;
+            let Some(position) = position else {
+                let mut errors = ValidationErrors::new();
+                errors.add("file", &format!("a review can have at most {} images", MAX_REVIEW_IMAGES));
+                return Err(errors.into());
+            };
+
+            sqlx::query!(
+                "UPDATE product_reviews SET status = 'pending', moderation_reason = NULL, moderated_by = NULL, moderated_at = NULL, updated_at = $1 WHERE id = $2",
+                image.created_at,
+                id
+            )
+                .execute(&mut *tx)
+                .await?;
+            apply_status_change(&mut tx, product_id, review.rating, review.status, ReviewStatus::Pending).await?;
+
+            tx.commit().await?;
+            Ok(position)
+        }.await;
+
+        match inserted {
+            Ok(position) => {
+                image.position = position;
+                Ok(image)
+            }
+            Err(error) => {
+                remove_image_objects(&image, storage).await;
+                Err(error)
+            }
+        }
+    }
+
+    async fn delete_image(&self, product_id: Uuid, id: Uuid, image_id: Uuid, user_id: Uuid, storage: &Storage, pool: &Pool<Postgres>) -> Result<(), ServiceError> {
+        self.fetch_own_review(product_id, id, user_id, pool).await?;
+
+        let image = sqlx::query_as!(ReviewImage, r#"
+                DELETE FROM review_images WHERE id = $1 AND review_id = $2
+                RETURNING id, review_id, storage_key, url, thumbnails AS "thumbnails: Json<BTreeMap<u32, String>>", content_type,
+                width, height, position, created_at"#, image_id, id)
+            .fetch_one(pool)
+            .await?;
+
+        remove_image_objects(&image, storage).await;
+        Ok(())
+    }
+    async fn save_response(&self, product_id: Uuid, id: Uuid, responder_id: Uuid, request: SaveReviewResponse, pool: &Pool<Postgres>) -> Result<ReviewResponse, ServiceError> {
+        validate_response(&request.body)?;
+        let review_exists = sqlx::query_scalar!(r#"SELECT EXISTS (SELECT 1 FROM product_reviews WHERE id = $1 AND product_id = $2) AS "exists!""#, id, product_id)
+            .fetch_one(pool)
+            .await?;
+        if !review_exists {
+            return Err(Error::RowNotFound.into());
+        }
+
+        let now = Utc::now().timestamp_millis();
+        let response = ReviewResponse {
+            review_id: id,
+            responder_id,
+            body: request.body,
+            created_at: now,
+            updated_at: now,
+        };
+
+        // Satu balasan per ulasan dijaga oleh primary key review_id
+        let inserted = sqlx::query!(
+            "INSERT INTO review_responses (review_id, responder_id, body, created_at, updated_at) VALUES ($1, $2, $3, $4, $5)",
+            response.review_id,
+            response.responder_id,
+            response.body,
+            response.created_at,
+            response.updated_at
+        ).execute(pool).await;
+        match inserted {
+            Err(Error::Database(error)) if error.is_unique_violation() => {
+                Err(ServiceError::Conflict("review already has a response, edit it instead".to_string()))
+            }
+            result => {
+                result?;
+                Ok(response)
+            }
+        }
+    }
+
+    async fn update_response(&self, product_id: Uuid, id: Uuid, responder_id: Uuid, request: SaveReviewResponse, pool: &Pool<Postgres>) -> Result<ReviewResponse, ServiceError> {
+        validate_response(&request.body)?;
+
+        let response = sqlx::query_as!(ReviewResponse, r#"
+                UPDATE review_responses SET body = $1, responder_id = $2, updated_at = $3
+                WHERE review_id = $4 AND EXISTS (SELECT 1 FROM product_reviews WHERE id = $4 AND product_id = $5)
+                RETURNING review_id, responder_id, body, created_at, updated_at"#,
+            request.body,
+            responder_id,
+            Utc::now().timestamp_millis(),
+            id,
+            product_id
+        )
+            .fetch_one(pool)
+            .await?;
+        Ok(response)
+    }
+
+    async fn delete_response(&self, product_id: Uuid, id: Uuid, pool: &Pool<Postgres>) -> Result<(), ServiceError> {
+        let delete = sqlx::query!(
+            "DELETE FROM review_responses WHERE review_id = $1 AND EXISTS (SELECT 1 FROM product_reviews WHERE id = $1 AND product_id = $2)",
+            id,
+            product_id
+        )
+            .execute(pool)
+            .await?;
+
+        match delete.rows_affected() {
+            1 => Ok(()),
+            _ => {
+                Err(Error::RowNotFound.into())
+            }
+        }
+    }
 }
\ No newline at end of file
@@ -1,41 +1,557 @@
-use bigdecimal::BigDecimal;
-use sqlx::{Error, Pool, Postgres, QueryBuilder};
+use sqlx::{Error, PgConnection, Pool, Postgres, QueryBuilder};
+use std::collections::HashSet;
 use sqlx::types::chrono::Utc;
 use uuid::Uuid;
-use crate::domain::product::{Product, ProductWithReviews, Review};
-use crate::services::category_service;
-use crate::services::service::{CategoryService, ProductService};
+use common::pagination::{decode_cursor, encode_cursor};
+use crate::domain::product::{CategoryFacet, CreateProduct, Product, ProductCursor, ProductPage, ProductQuery, ProductSearchHit, ProductSearchQuery, ProductSearchResult, ProductSort, ProductWithReviews, RatingSummary, Review, SortOrder, UpdateProduct};
+use crate::domain::stock_movement::{StockMovement, StockMovementReason};
+use crate::services::{product_image_service, product_price_service, product_review_service, product_variant_service, stock_movement_service};
+use crate::services::error::ServiceError;
+use crate::services::service::{ProductImageService, ProductService, ProductVariantService};
+
+const DEFAULT_PAGE_SIZE: i64 = 20;
+const MAX_PAGE_SIZE: i64 = 100;
+const DEFAULT_SEARCH_LIMIT: i64 = 20;
+const SIMILARITY_THRESHOLD: f32 = 0.4;
+pub(crate) const MAX_SKU_LENGTH: usize = 100;
+const MAX_SLUG_LENGTH: usize = 100;
+// Jumlah unit yang sedang ditahan reservasi aktif yang belum lewat TTL
+const RESERVED_STOCK: &str = "COALESCE((SELECT SUM(quantity) FROM inventory_reservations \
+    WHERE inventory_reservations.product_id = products.id AND status = 'active' \
+    AND expires_at > (extract(epoch from now()) * 1000)::bigint), 0)::integer";
 
 pub struct ProductServiceImpl;
 
+fn select_products() -> String {
+    format!("SELECT products.id, products.sku, products.slug, products.barcode, products.name, description, price, stock, \
+    {reserved} AS reserved_stock, stock - {reserved} AS available_stock, products.reorder_threshold, products.version, \
+    products.created_at, products.updated_at, products.deleted_at, categories.name as category_name, product_images.url as primary_image_url, \
+    COALESCE(product_ratings.review_count, 0) AS review_count, product_ratings.rating_average AS average_rating, \
+    ARRAY[COALESCE(product_ratings.rating_1, 0), COALESCE(product_ratings.rating_2, 0), COALESCE(product_ratings.rating_3, 0), \
+    COALESCE(product_ratings.rating_4, 0), COALESCE(product_ratings.rating_5, 0)] AS rating_histogram \
+    FROM products INNER JOIN categories ON categories.id = products.category_id \
+    LEFT JOIN product_ratings ON product_ratings.product_id = products.id \
+    LEFT JOIN product_images ON product_images.product_id = products.id AND product_images.is_primary", reserved = RESERVED_STOCK)
+}
+
+fn sort_column(sort: ProductSort) -> &'static str {
+    match sort {
+        ProductSort::Price => "products.price",
+        ProductSort::CreatedAt => "products.created_at",
+        ProductSort::Name => "products.name",
+        ProductSort::Stock => "products.stock",
+        ProductSort::Rating => "COALESCE(product_ratings.rating_average, 0)",
+    }
+}
+
+fn sort_cast(sort: ProductSort) -> &'static str {
+    match sort {
+        ProductSort::Price => "numeric",
+        ProductSort::CreatedAt => "bigint",
+        ProductSort::Name => "text",
+        ProductSort::Stock => "integer",
+        ProductSort::Rating => "numeric",
+    }
+}
+
+fn product_cursor(product: &Product, sort: ProductSort, order: SortOrder, backward: bool) -> ProductCursor {
+    let value = match sort {
+        ProductSort::Price => product.price.to_string(),
+        ProductSort::CreatedAt => product.created_at.to_string(),
+        ProductSort::Name => product.name.clone(),
+        ProductSort::Stock => product.stock.to_string(),
+        ProductSort::Rating => product.rating.average_rating.as_ref().map(|rating| rating.to_string()).unwrap_or("0".to_string()),
+    };
+    ProductCursor { sort, order, value, id: product.id, backward }
+}
+
+// Cursor hanya berlaku untuk sort dan order yang sama dengan saat dibuat
+fn parse_cursor(cursor: &str, sort: ProductSort, order: SortOrder) -> Result<ProductCursor, Error> {
+    decode_cursor(cursor)
+        .filter(|cursor: &ProductCursor| cursor.sort == sort && cursor.order == order)
+        .ok_or_else(|| Error::Protocol("invalid cursor".to_string()))
+}
+
+fn push_filters(query_builder: &mut QueryBuilder<Postgres>, query: &ProductQuery) {
+    if let Some(category_id) = query.category_id {
+        if query.include_descendants == Some(true) {
+            query_builder.push(" AND products.category_id IN (WITH RECURSIVE subtree AS (SELECT id FROM categories WHERE id = ");
+            query_builder.push_bind(category_id);
+            query_builder.push(" UNION ALL SELECT c.id FROM categories c INNER JOIN subtree ON c.parent_id = subtree.id) SELECT id FROM subtree)");
+        } else {
+            query_builder.push(" AND products.category_id = ");
+            query_builder.push_bind(category_id);
+        }
+    }
+    if let Some(min_price) = &query.min_price {
+        query_builder.push(" AND products.price >= ");
+        query_builder.push_bind(min_price.clone());
+    }
+    if let Some(max_price) = &query.max_price {
+        query_builder.push(" AND products.price <= ");
+        query_builder.push_bind(max_price.clone());
+    }
+    if query.in_stock == Some(true) {
+        query_builder.push(" AND products.stock > 0");
+    }
+    if let Some(min_rating) = &query.min_rating {
+        query_builder.push(" AND COALESCE(product_ratings.rating_average, 0) >= ");
+        query_builder.push_bind(min_rating.clone());
+    }
+    if query.include_deleted != Some(true) {
+        query_builder.push(" AND products.deleted_at IS NULL");
+    }
+}
+
+fn slugify(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    for character in name.to_lowercase().chars() {
+        if character.is_ascii_alphanumeric() {
+            slug.push(character);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.truncate(MAX_SLUG_LENGTH);
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() { "product".to_string() } else { slug.to_string() }
+}
+
+/// Cek digit GTIN/EAN: dari kanan, digit sebelum check digit diberi bobot 3 dan 1 bergantian.
+pub(crate) fn is_valid_gtin(code: &str) -> bool {
+    if !matches!(code.len(), 8 | 12 | 13 | 14) || !code.bytes().all(|byte| byte.is_ascii_digit()) {
+        return false;
+    }
+    let digits: Vec<u32> = code.bytes().map(|byte| u32::from(byte - b'0')).collect();
+    let Some((check_digit, body)) = digits.split_last() else {
+        return false;
+    };
+    let sum: u32 = body.iter().rev().enumerate()
+        .map(|(index, digit)| if index % 2 == 0 { digit * 3 } else { *digit })
+        .sum();
+    (10 - sum % 10) % 10 == *check_digit
+}
+
+pub(crate) fn normalize_sku(sku: Option<String>) -> Result<Option<String>, Error> {
+    let sku = sku.map(|sku| sku.trim().to_string()).filter(|sku| !sku.is_empty());
+    if sku.as_ref().is_some_and(|sku| sku.chars().count() > MAX_SKU_LENGTH) {
+        return Err(Error::Protocol(format!("sku must be at most {} characters", MAX_SKU_LENGTH)));
+    }
+    Ok(sku)
+}
+
+pub(crate) fn normalize_barcode(barcode: Option<String>) -> Result<Option<String>, Error> {
+    let barcode = barcode.map(|barcode| barcode.trim().to_string()).filter(|barcode| !barcode.is_empty());
+    if barcode.as_deref().is_some_and(|barcode| !is_valid_gtin(barcode)) {
+        return Err(Error::Protocol("barcode must be a GTIN-8, GTIN-12, EAN-13 or GTIN-14 with a valid check digit".to_string()));
+    }
+    Ok(barcode)
+}
+
+/// Slug dibuat dari nama produk; kalau sudah dipakai diberi akhiran -2, -3 dan seterusnya.
+async fn unique_slug(conn: &mut PgConnection, name: &str) -> Result<String, Error> {
+    let base = slugify(name);
+    // Serialisasi per slug dasar supaya dua produk bernama sama tidak memilih akhiran yang sama
+    sqlx::query!("SELECT pg_advisory_xact_lock(hashtext('product_slug:' || $1))", base)
+        .execute(&mut *conn)
+        .await?;
+    let taken: HashSet<String> = sqlx::query_scalar!("SELECT slug FROM products WHERE slug = $1 OR slug LIKE $2", base, format!("{}-%", base))
+        .fetch_all(&mut *conn)
+        .await?
+        .into_iter()
+        .collect();
+
+    let mut slug = base.clone();
+    let mut suffix = 2;
+    while taken.contains(&slug) {
+        slug = format!("{}-{}", base, suffix);
+        suffix += 1;
+    }
+    Ok(slug)
+}
+
+/// Menyimpan produk baru di koneksi milik pemanggil, supaya import bisa menggabungkan banyak produk dalam satu transaksi.
+pub(crate) async fn insert_product(conn: &mut PgConnection, request: CreateProduct) -> Result<Product, Error> {
+    if request.stock < 0 {
+        return Err(Error::Protocol("stock must not be negative".to_string()));
+    }
+    let reorder_threshold = request.reorder_threshold.unwrap_or(0);
+    if reorder_threshold < 0 {
+        return Err(Error::Protocol("reorder_threshold must not be negative".to_string()));
+    }
+    let sku = normalize_sku(request.sku)?;
+    let barcode = normalize_barcode(request.barcode)?;
+    let category = sqlx::query!("SELECT id, name, deleted_at FROM categories WHERE id = $1", request.category_id)
+        .fetch_one(&mut *conn)
+        .await?;
+    if category.deleted_at.is_some() {
+        return Err(Error::Protocol("category is archived".to_string()));
+    }
+    let slug = unique_slug(conn, &request.name).await?;
+
+    let now = Utc::now().timestamp_millis();
+    let product = Product {
+        id: Uuid::new_v4(),
+        sku,
+        slug,
+        barcode,
+        name: request.name,
+        description: request.description,
+        price: request.price,
+        stock: request.stock,
+        reserved_stock: 0,
+        available_stock: request.stock,
+        reorder_threshold,
+        rating: RatingSummary::default(),
+        category_name: category.name,
+        primary_image_url: None,
+        images: None,
+        variants: None,
+        version: 0,
+        created_at: now,
+        updated_at: now,
+        deleted_at: None,
+    };
+
+    sqlx::query!(
+    "INSERT INTO products (id, sku, slug, barcode, name, description, price, stock, reorder_threshold, category_id, created_at, updated_at, version) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)",
+        product.id,
+        product.sku,
+        product.slug,
+        product.barcode,
+        product.name,
+        product.description,
+        product.price,
+        product.stock,
+        product.reorder_threshold,
+        category.id,
+        product.created_at,
+        product.updated_at,
+        product.version
+    ).execute(&mut *conn).await?;
+
+    product_price_service::record_price_change(conn, product.id, &product.price, None, "system", now).await?;
+
+    // Stok awal dicatat sebagai restock supaya total ledger tetap sama dengan stock
+    if product.stock > 0 {
+        let movement = StockMovement {
+            id: Uuid::new_v4(),
+            product_id: product.id,
+            reason: StockMovementReason::Restock,
+            quantity: product.stock,
+            balance_after: product.stock,
+            actor: "system".to_string(),
+            reference: None,
+            created_at: now,
+        };
+        stock_movement_service::insert_movement(conn, &movement).await?;
+    }
+
+    Ok(product)
+}
+
+/// Mengubah produk di koneksi milik pemanggil. Mengembalikan false kalau tidak ada field yang berubah.
+pub(crate) async fn update_product(conn: &mut PgConnection, id: Uuid, request: UpdateProduct, version: i32, actor: &str) -> Result<bool, ServiceError> {
+    let current = sqlx::query!("SELECT price, version FROM products WHERE id = $1 FOR UPDATE", id)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    if current.version != version {
+        return Err(ServiceError::VersionConflict { expected: version, current: current.version });
+    }
+
+    let mut query_builder = QueryBuilder::<Postgres>::new("UPDATE products SET ");
+    let mut first = true;
+    if let Some(sku) = normalize_sku(request.sku)? {
+        query_builder.push("sku = ");
+        query_builder.push_bind(sku);
+        first = false;
+    }
+    if let Some(barcode) = normalize_barcode(request.barcode)? {
+        if !first { query_builder.push(", ");}
+        query_builder.push("barcode = ");
+        query_builder.push_bind(barcode);
+        first = false;
+    }
+    if let Some(name) = &request.name {
+        if !first { query_builder.push(", ");}
+        query_builder.push("name = ");
+        query_builder.push_bind(name);
+        first = false;
+    }
+    if let Some(description) = &request.description {
+        if !first { query_builder.push(", ");}
+        query_builder.push("description = ");
+        query_builder.push_bind(description);
+        first = false;
+    }
+    let mut price_change = None;
+    if let Some(price) = request.price.as_ref().filter(|price| **price != current.price) {
+        if !first { query_builder.push(", ");}
+        query_builder.push("price = ");
+        query_builder.push_bind(price);
+        price_change = Some(price);
+        first = false;
+    }
+    if let Some(reorder_threshold) = &request.reorder_threshold {
+        if *reorder_threshold < 0 {
+            return Err(Error::Protocol("reorder_threshold must not be negative".to_string()).into());
+        }
+        if !first { query_builder.push(", ");}
+        query_builder.push("reorder_threshold = ");
+        query_builder.push_bind(reorder_threshold);
+        first = false;
+    }
+
+    if first {
+        // Tidak ada data yang diupdate
+        return Ok(false);
+    }
+
+    let updated_at = Utc::now().timestamp_millis();
+    query_builder.push(", version = ");
+    query_builder.push_bind(version + 1);
+    query_builder.push(", updated_at = ");
+    query_builder.push_bind(updated_at);
+    query_builder.push(" WHERE id = ");
+    query_builder.push_bind(id);
+
+    query_builder.build().execute(&mut *conn).await?;
+    if let Some(price) = price_change {
+        product_price_service::record_price_change(conn, id, price, Some(&current.price), actor, updated_at).await?;
+    }
+    Ok(true)
+}
+
 impl ProductService for ProductServiceImpl {
-    async fn fetch_all(&self, pool: &Pool<Postgres>) -> Result<Vec<Product>, Error> {
-        let products = sqlx::query_as!(Product, "SELECT products.id, products.name, description, price, stock, \
-        products.created_at, products.updated_at, categories.name as category_name \
-        FROM products INNER JOIN categories ON categories.id = products.category_id")
+    async fn fetch_all(&self, query: ProductQuery, pool: &Pool<Postgres>) -> Result<ProductPage, Error> {
+        let sort = query.sort.unwrap_or_default();
+        let order = query.order.unwrap_or_default();
+        let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+
+        let cursor = query.cursor.as_deref()
+            .map(|cursor| parse_cursor(cursor, sort, order))
+            .transpose()?;
+        let backward = cursor.as_ref().is_some_and(|cursor| cursor.backward);
+        // Saat mundur, urutan dibalik lalu hasilnya dibalik lagi setelah query
+        let ascending = (order == SortOrder::Asc) != backward;
+
+        let mut query_builder = QueryBuilder::<Postgres>::new(select_products());
+        query_builder.push(" WHERE 1 = 1");
+        push_filters(&mut query_builder, &query);
+
+        if let Some(cursor) = &cursor {
+            query_builder.push(format!(" AND ({}, products.id) {} (", sort_column(sort), if ascending { ">" } else { "<" }));
+            query_builder.push_bind(cursor.value.clone());
+            query_builder.push(format!("::{}, ", sort_cast(sort)));
+            query_builder.push_bind(cursor.id);
+            query_builder.push(")");
+        }
+
+        let direction = if ascending { "ASC" } else { "DESC" };
+        query_builder.push(format!(" ORDER BY {} {}, products.id {} LIMIT ", sort_column(sort), direction, direction));
+        query_builder.push_bind(limit + 1);
+
+        let mut products = query_builder.build_query_as::<Product>()
+            .fetch_all(pool)
+            .await?;
+
+        let has_more = products.len() as i64 > limit;
+        products.truncate(limit as usize);
+        if backward {
+            products.reverse();
+        }
+
+        let (has_next, has_prev) = match (&cursor, backward) {
+            (None, _) => (has_more, false),
+            (Some(_), false) => (has_more, true),
+            (Some(_), true) => (true, has_more),
+        };
+        let next_cursor = products.last()
+            .filter(|_| has_next)
+            .map(|product| encode_cursor(&product_cursor(product, sort, order, false)));
+        let prev_cursor = products.first()
+            .filter(|_| has_prev)
+            .map(|product| encode_cursor(&product_cursor(product, sort, order, true)));
+
+        let mut count_builder = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM products \
+        LEFT JOIN product_ratings ON product_ratings.product_id = products.id WHERE 1 = 1");
+        push_filters(&mut count_builder, &query);
+        let total: i64 = count_builder.build_query_scalar()
+            .fetch_one(pool)
+            .await?;
+
+        Ok(ProductPage {
+            products,
+            next_cursor,
+            prev_cursor,
+            total,
+        })
+    }
+
+    async fn fetch_low_stock(&self, pool: &Pool<Postgres>) -> Result<Vec<Product>, Error> {
+        let mut query_builder = QueryBuilder::<Postgres>::new(select_products());
+        query_builder.push(" WHERE products.deleted_at IS NULL AND products.reorder_threshold > 0 \
+        AND products.stock <= products.reorder_threshold \
+        ORDER BY products.stock - products.reorder_threshold, products.name");
+
+        let products = query_builder.build_query_as::<Product>()
             .fetch_all(pool)
             .await?;
         Ok(products)
     }
 
     async fn fetch_by_id(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<Product, Error> {
-        let product = sqlx::query_as!(Product, "SELECT products.id, products.name, description, price, stock, \
-        products.created_at, products.updated_at, categories.name as category_name \
-        FROM products INNER JOIN categories ON categories.id = products.category_id WHERE products.id = $1", id)
+        let row = sqlx::query!(r#"SELECT products.id, products.sku, products.slug, products.barcode, products.name, description, price, stock,
+        COALESCE((SELECT SUM(quantity) FROM inventory_reservations
+            WHERE inventory_reservations.product_id = products.id AND status = 'active'
+            AND expires_at > (extract(epoch from now()) * 1000)::bigint), 0)::integer AS "reserved_stock!", products.reorder_threshold, products.version,
+        products.created_at, products.updated_at, products.deleted_at, categories.name as category_name
+        FROM products INNER JOIN categories ON categories.id = products.category_id WHERE products.id = $1"#, id)
             .fetch_one(pool)
             .await?;
-        Ok(product)
+
+        let rating = product_review_service::fetch_rating_summary(id, pool).await?;
+        let images = product_image_service::ProductImageServiceImpl.fetch_images(id, pool).await?;
+        let variants = product_variant_service::ProductVariantServiceImpl.fetch_variants(id, pool).await?;
+
+        Ok(Product {
+            id: row.id,
+            sku: row.sku,
+            slug: row.slug,
+            barcode: row.barcode,
+            name: row.name,
+            description: row.description,
+            price: row.price,
+            stock: row.stock,
+            reserved_stock: row.reserved_stock,
+            available_stock: row.stock - row.reserved_stock,
+            reorder_threshold: row.reorder_threshold,
+            rating,
+            category_name: row.category_name,
+            primary_image_url: images.iter().find(|image| image.is_primary).map(|image| image.url.clone()),
+            images: Some(images),
+            variants: Some(variants),
+            version: row.version,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            deleted_at: row.deleted_at,
+        })
+    }
+
+    async fn fetch_by_slug(&self, slug: &str, pool: &Pool<Postgres>) -> Result<Product, Error> {
+        let id = sqlx::query_scalar!("SELECT id FROM products WHERE slug = $1 AND deleted_at IS NULL", slug)
+            .fetch_one(pool)
+            .await?;
+        self.fetch_by_id(id, pool).await
+    }
+
+    async fn fetch_by_sku(&self, sku: &str, pool: &Pool<Postgres>) -> Result<Product, Error> {
+        // SKU produk dan varian satu namespace, jadi SKU varian mengembalikan produk induknya
+        let id = sqlx::query_scalar!(
+            "SELECT id AS \"id!\" FROM products WHERE sku = $1 AND deleted_at IS NULL \
+            UNION ALL SELECT product_id FROM product_variants \
+            INNER JOIN products ON products.id = product_variants.product_id \
+            WHERE product_variants.sku = $1 AND products.deleted_at IS NULL",
+            sku
+        )
+            .fetch_one(pool)
+            .await?;
+        self.fetch_by_id(id, pool).await
+    }
+
+    async fn search(&self, query: ProductSearchQuery, pool: &Pool<Postgres>) -> Result<ProductSearchResult, Error> {
+        let q = query.q.trim();
+        if q.is_empty() {
+            return Err(Error::Protocol("search query must not be empty".to_string()));
+        }
+        let limit = query.limit.unwrap_or(DEFAULT_SEARCH_LIMIT).clamp(1, MAX_PAGE_SIZE);
+
+        // Full-text match ditambah trigram word similarity agar typo tetap ketemu. Operator <% memakai index
+        // trigram dengan ambang pg_trgm.word_similarity_threshold, jadi ambangnya di-set untuk transaksi ini saja
+        let mut tx = pool.begin().await?;
+        sqlx::query("SELECT set_config('pg_trgm.word_similarity_threshold', $1, true)")
+            .bind(SIMILARITY_THRESHOLD.to_string())
+            .execute(&mut *tx)
+            .await?;
+
+        let rows = sqlx::query!(r#"
+                SELECT products.id, products.name, description, price, stock, products.created_at, products.updated_at,
+                categories.name AS category_name,
+                (ts_rank(products.search_vector, websearch_to_tsquery('simple', $1)) + GREATEST(word_similarity($1, products.name), word_similarity($1, coalesce(products.description, ''))))::real AS "rank!",
+                ts_headline('simple', products.name || ' ' || coalesce(description, ''), websearch_to_tsquery('simple', $1),
+                    'StartSel=<mark>, StopSel=</mark>, MaxFragments=2, MaxWords=30, MinWords=10') AS "highlight!",
+                COALESCE(product_ratings.review_count, 0) AS "review_count!", product_ratings.rating_average AS "average_rating?",
+                ARRAY[COALESCE(product_ratings.rating_1, 0), COALESCE(product_ratings.rating_2, 0), COALESCE(product_ratings.rating_3, 0),
+                COALESCE(product_ratings.rating_4, 0), COALESCE(product_ratings.rating_5, 0)] AS "rating_histogram!"
+                FROM products
+                INNER JOIN categories ON categories.id = products.category_id
+                LEFT JOIN product_ratings ON product_ratings.product_id = products.id
+                WHERE (products.search_vector @@ websearch_to_tsquery('simple', $1) OR $1 <% products.name OR $1 <% products.description)
+                AND ($2::uuid IS NULL OR products.category_id = $2)
+                AND products.deleted_at IS NULL
+                ORDER BY 9 DESC, products.id
+                LIMIT $3"#, q, query.category_id, limit)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        let hits = rows.into_iter()
+            .map(|row| ProductSearchHit {
+                id: row.id,
+                name: row.name,
+                description: row.description,
+                price: row.price,
+                stock: row.stock,
+                category_name: row.category_name,
+                rating: RatingSummary {
+                    review_count: row.review_count,
+                    average_rating: row.average_rating,
+                    rating_histogram: row.rating_histogram,
+                },
+                rank: row.rank,
+                highlight: row.highlight,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            })
+            .collect();
+
+        let total = sqlx::query_scalar!(r#"
+                SELECT COUNT(*) AS "count!" FROM products
+                WHERE (products.search_vector @@ websearch_to_tsquery('simple', $1) OR $1 <% products.name OR $1 <% products.description)
+                AND ($2::uuid IS NULL OR products.category_id = $2)
+                AND products.deleted_at IS NULL"#, q, query.category_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        let facets = sqlx::query_as!(CategoryFacet, r#"
+                SELECT categories.id AS category_id, categories.name AS category_name, COUNT(*) AS "count!"
+                FROM products
+                INNER JOIN categories ON categories.id = products.category_id
+                WHERE (products.search_vector @@ websearch_to_tsquery('simple', $1) OR $1 <% products.name OR $1 <% products.description)
+                AND products.deleted_at IS NULL
+                GROUP BY categories.id, categories.name
+                ORDER BY 3 DESC, categories.name"#, q)
+            .fetch_all(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(ProductSearchResult {
+            hits,
+            facets,
+            total,
+        })
     }
 
     async fn fetch_by_id_with_reviews(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<ProductWithReviews, Error> {
 
         let rows = sqlx::query!(r#"
-                SELECT products.id AS product_id, products.name, description, price, stock, products.created_at, products.updated_at,
+                SELECT products.id AS product_id, products.name, description, price, stock, products.version, products.created_at, products.updated_at, products.deleted_at,
                 categories.name as category_name,
-                product_reviews.product_id as review_product_id, product_reviews.user_id, product_reviews.comment, product_reviews.rating, product_reviews.created_at as review_created_at
+                product_reviews.id AS "review_id?", product_reviews.product_id AS "review_product_id?", product_reviews.user_id AS "user_id?", product_reviews.comment, product_reviews.rating,
+                product_reviews.helpful_count AS "helpful_count?", product_reviews.unhelpful_count AS "unhelpful_count?", product_reviews.verified_purchase AS "verified_purchase?", product_reviews.created_at AS "review_created_at?"
                 FROM products
                 LEFT JOIN categories ON products.category_id = categories.id
-                LEFT JOIN product_reviews  ON products.id = product_reviews.product_id
+                LEFT JOIN product_reviews  ON products.id = product_reviews.product_id AND product_reviews.status = 'approved'
                 WHERE products.id = $1"#, id)
             .fetch_all(pool)
             .await?;
@@ -45,6 +561,11 @@ impl ProductService for ProductServiceImpl {
             return Err(Error::RowNotFound); // Atau penanganan kesalahan lain yang sesuai
         }
 
+        let rating = product_review_service::fetch_rating_summary(id, pool).await?;
+        let review_ids: Vec<Uuid> = rows.iter().filter_map(|row| row.review_id).collect();
+        let mut images_by_review = product_review_service::fetch_review_images(&review_ids, pool).await?;
+        let mut responses = product_review_service::fetch_review_responses(&review_ids, pool).await?;
+
         // Memetakan hasil ke dalam ProductWithReviews
         let mut product = ProductWithReviews {
             id: rows[0].product_id, // Ambil dari baris pertama
@@ -53,19 +574,28 @@ impl ProductService for ProductServiceImpl {
             price: rows[0].price.clone(),
             stock: rows[0].stock,
             category_name: rows[0].category_name.clone(),
+            rating,
             reviews: None, // Inisialisasi sebagai None
+            version: rows[0].version,
             created_at: rows[0].created_at,
             updated_at: rows[0].updated_at,
+            deleted_at: rows[0].deleted_at,
         };
         // Mengumpulkan ulasan
         let mut reviews: Vec<Review> = Vec::new();
         for row in rows {
-            if let Some(user_id) = row.user_id {
+            if row.user_id.is_some() {
                 reviews.push(Review {
+                    id: row.review_id,
                     user_id: row.user_id,
                     comment: row.comment.clone(),
                     rating: row.rating,
-                    created_at: Some(row.review_created_at),
+                    helpful_count: row.helpful_count.unwrap_or(0),
+                    unhelpful_count: row.unhelpful_count.unwrap_or(0),
+                    images: row.review_id.and_then(|review_id| images_by_review.remove(&review_id)).unwrap_or_default(),
+                    response: row.review_id.and_then(|review_id| responses.remove(&review_id)),
+                    verified_purchase: row.verified_purchase.unwrap_or(false),
+                    created_at: row.review_created_at,
                 });
             }
         }
@@ -78,97 +608,127 @@ impl ProductService for ProductServiceImpl {
         Ok(product)
     }
 
-    async fn save(&self, name: String, description: Option<String>, price: BigDecimal, stock: i32, category_id: Uuid, pool: &Pool<Postgres>) -> Result<Product, Error> {
-        let category = category_service::CategoryServiceImpl.fetch_by_id(category_id, pool).await?;
-
-        let now = Utc::now().timestamp_millis();
-        let product = Product {
-            id: Uuid::new_v4(),
-            name,
-            description,
-            price,
-            stock,
-            category_name: category.name,
-            created_at: now,
-            updated_at: now,
-        };
-
-        sqlx::query!(
-        "INSERT INTO products (id, name, description, price, stock, category_id, created_at, updated_at, version) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
-            product.id,
-            product.name,
-            product.description,
-            product.price,
-            product.stock,
-            category.id,
-            product.created_at,
-            product.updated_at,
-            0
-        ).execute(pool).await?;
-
+    async fn save(&self, request: CreateProduct, pool: &Pool<Postgres>) -> Result<Product, Error> {
+        let mut tx = pool.begin().await?;
+        let product = insert_product(&mut tx, request).await?;
+        tx.commit().await?;
         Ok(product)
     }
 
-    async fn update(&self, id: Uuid, name: Option<String>, description: Option<String>, price: Option<BigDecimal>, stock: Option<i32>, pool: &Pool<Postgres>) -> Result<Product, Error> {
-        let mut product = self.fetch_by_id(id, pool).await?;
+    async fn update(&self, id: Uuid, actor_id: Uuid, request: UpdateProduct, version: i32, pool: &Pool<Postgres>) -> Result<Product, ServiceError> {
+        let mut tx = pool.begin().await?;
+        update_product(&mut tx, id, request, version, &actor_id.to_string()).await?;
+        tx.commit().await?;
+        Ok(self.fetch_by_id(id, pool).await?)
+    }
 
+    async fn delete(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<(), Error> {
+        // Produk hanya diarsipkan supaya riwayat order dan ulasan tetap valid
+        let archive = sqlx::query!(
+            "UPDATE products SET deleted_at = $1, updated_at = $1, version = version + 1 WHERE id = $2 AND deleted_at IS NULL",
+            Utc::now().timestamp_millis(),
+            id
+        )
+            .execute(pool)
+            .await?;
 
-        let mut query_builder = QueryBuilder::<Postgres>::new("UPDATE products SET ");
-        let mut first = true;
-        if let Some(name) = &name {
-            if !first { query_builder.push(", ");}
-            query_builder.push("name = ");
-            query_builder.push_bind(name);
-            product.name = name.clone();
-            first = false;
-        }
-        if let Some(description) = &description {
-            if !first { query_builder.push(", ");}
-            query_builder.push("description = ");
-            query_builder.push_bind(description);
-            product.description = Some(description.clone());
-            first = false;
+        match archive.rows_affected() {
+            1 => Ok(()),
+            _ => {
+                Err(Error::RowNotFound)
+            }
         }
-        if let Some(price) = &price {
-            if !first { query_builder.push(", ");}
-            query_builder.push("price = ");
-            query_builder.push_bind(price);
-            product.price = price.clone();
-            first = false;
+    }
+
+    async fn restore(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<Product, Error> {
+        let row = sqlx::query!(r#"
+                SELECT products.deleted_at, categories.deleted_at AS category_deleted_at
+                FROM products INNER JOIN categories ON categories.id = products.category_id
+                WHERE products.id = $1"#, id)
+            .fetch_one(pool)
+            .await?;
+        if row.deleted_at.is_none() {
+            return Err(Error::Protocol("product is not archived".to_string()));
         }
-        if let Some(stock) = &stock {
-            if !first { query_builder.push(", ");}
-            query_builder.push("stock = ");
-            query_builder.push_bind(stock);
-            product.stock = stock.clone();
-            first = false;
+        if row.category_deleted_at.is_some() {
+            return Err(Error::Protocol("product category is archived, restore it first".to_string()));
         }
 
-        if first {
-            // Tidak ada data yang diupdate
-            return Ok(product);
-        }
+        sqlx::query!(
+            "UPDATE products SET deleted_at = NULL, updated_at = $1, version = version + 1 WHERE id = $2 AND deleted_at IS NOT NULL",
+            Utc::now().timestamp_millis(),
+            id
+        )
+            .execute(pool)
+            .await?;
 
-        query_builder.push(" WHERE id = ");
-        query_builder.push_bind(id);
+        self.fetch_by_id(id, pool).await
+    }
+}
 
-        let query = query_builder.build();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        // Melakukan update di database
-        query.execute(pool).await?;
-        Ok(product)
+    #[test]
+    fn gtin_accepts_valid_check_digits() {
+        assert!(is_valid_gtin("4006381333931"));
+        assert!(is_valid_gtin("96385074"));
+        assert!(is_valid_gtin("036000291452"));
+        assert!(is_valid_gtin("10614141000415"));
     }
 
-    async fn delete(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<(), Error> {
-        let delete = sqlx::query!("DELETE FROM products WHERE id = $1", id)
-            .execute(pool)
-            .await;
+    #[test]
+    fn gtin_rejects_invalid_codes() {
+        assert!(!is_valid_gtin("4006381333932"));
+        assert!(!is_valid_gtin("400638133393"));
+        assert!(!is_valid_gtin("40063813339a1"));
+        assert!(!is_valid_gtin(""));
+    }
 
-        match delete.unwrap_or_default().rows_affected() {
-            1 => Ok(()),
-            _ => {
-                Err(Error::RowNotFound)
-            }
-        }
+    #[test]
+    fn slugify_collapses_punctuation() {
+        assert_eq!(slugify("  Kopi Susu -- Gula Aren! "), "kopi-susu-gula-aren");
+        assert_eq!(slugify("iPhone 15 Pro (256GB)"), "iphone-15-pro-256gb");
+    }
+
+    #[test]
+    fn slugify_falls_back_for_empty_names() {
+        assert_eq!(slugify(""), "product");
+        assert_eq!(slugify("!!! ???"), "product");
+    }
+
+    #[test]
+    fn slugify_truncates_without_trailing_dash() {
+        let slug = slugify(&format!("{} b", "a".repeat(MAX_SLUG_LENGTH - 1)));
+        assert_eq!(slug, "a".repeat(MAX_SLUG_LENGTH - 1));
+    }
+
+    fn cursor(sort: ProductSort, order: SortOrder, backward: bool) -> ProductCursor {
+        ProductCursor { sort, order, value: "15000.00".to_string(), id: Uuid::new_v4(), backward }
+    }
+
+    #[test]
+    fn product_cursor_round_trips() {
+        let original = cursor(ProductSort::Price, SortOrder::Desc, true);
+        let parsed = parse_cursor(&encode_cursor(&original), ProductSort::Price, SortOrder::Desc).unwrap();
+        assert_eq!(parsed.sort, original.sort);
+        assert_eq!(parsed.order, original.order);
+        assert_eq!(parsed.value, original.value);
+        assert_eq!(parsed.id, original.id);
+        assert!(parsed.backward);
+    }
+
+    #[test]
+    fn product_cursor_rejects_malformed_input() {
+        assert!(matches!(parse_cursor("not a cursor", ProductSort::Price, SortOrder::Asc), Err(Error::Protocol(_))));
+        assert!(matches!(parse_cursor(&encode_cursor(&"price"), ProductSort::Price, SortOrder::Asc), Err(Error::Protocol(_))));
+    }
+
+    #[test]
+    fn product_cursor_rejects_mismatched_sort_or_order() {
+        let encoded = encode_cursor(&cursor(ProductSort::Price, SortOrder::Asc, false));
+        assert!(matches!(parse_cursor(&encoded, ProductSort::Name, SortOrder::Asc), Err(Error::Protocol(_))));
+        assert!(matches!(parse_cursor(&encoded, ProductSort::Price, SortOrder::Desc), Err(Error::Protocol(_))));
     }
-}
\ No newline at end of file
+}
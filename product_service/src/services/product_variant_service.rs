@@ -0,0 +1,193 @@
+use std::collections::BTreeMap;
+use sqlx::{Error, Pool, Postgres, QueryBuilder};
+use sqlx::types::chrono::Utc;
+use sqlx::types::Json;
+use uuid::Uuid;
+use crate::domain::product_variant::{CreateProductOptionType, CreateProductVariant, ProductOptionType, ProductVariant, UpdateProductVariant};
+use crate::services::product_service::{normalize_barcode, normalize_sku};
+use crate::services::service::ProductVariantService;
+
+pub struct ProductVariantServiceImpl;
+
+fn validate_options(options: &BTreeMap<String, String>, option_types: &[ProductOptionType]) -> Result<(), Error> {
+    if options.len() != option_types.len() {
+        return Err(Error::Protocol("variant must specify exactly one value for every option type".to_string()));
+    }
+    for option_type in option_types {
+        match options.get(&option_type.name) {
+            Some(value) if option_type.values.contains(value) => {}
+            Some(value) => return Err(Error::Protocol(format!("'{}' is not a valid value for option '{}'", value, option_type.name))),
+            None => return Err(Error::Protocol(format!("missing value for option '{}'", option_type.name))),
+        }
+    }
+    Ok(())
+}
+
+impl ProductVariantService for ProductVariantServiceImpl {
+    async fn fetch_option_types(&self, product_id: Uuid, pool: &Pool<Postgres>) -> Result<Vec<ProductOptionType>, Error> {
+        let option_types = sqlx::query_as!(ProductOptionType, r#"
+                SELECT id, product_id, name, option_values AS values, created_at, updated_at
+                FROM product_option_types WHERE product_id = $1 ORDER BY created_at"#, product_id)
+            .fetch_all(pool)
+            .await?;
+        Ok(option_types)
+    }
+
+    async fn save_option_type(&self, product_id: Uuid, option_type: CreateProductOptionType, pool: &Pool<Postgres>) -> Result<ProductOptionType, Error> {
+        if option_type.values.is_empty() {
+            return Err(Error::Protocol("option type must have at least one value".to_string()));
+        }
+        let has_variants = sqlx::query_scalar!(r#"SELECT EXISTS (SELECT 1 FROM product_variants WHERE product_id = $1) AS "exists!""#, product_id)
+            .fetch_one(pool)
+            .await?;
+        if has_variants {
+            return Err(Error::Protocol("option types cannot be added while the product has variants".to_string()));
+        }
+
+        let now = Utc::now().timestamp_millis();
+        let option_type = ProductOptionType {
+            id: Uuid::new_v4(),
+            product_id,
+            name: option_type.name,
+            values: option_type.values,
+            created_at: now,
+            updated_at: now,
+        };
+
+        sqlx::query!(
+        "INSERT INTO product_option_types (id, product_id, name, option_values, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6)",
+            option_type.id,
+            option_type.product_id,
+            option_type.name,
+            &option_type.values,
+            option_type.created_at,
+            option_type.updated_at
+        ).execute(pool).await?;
+
+        Ok(option_type)
+    }
+
+    async fn delete_option_type(&self, product_id: Uuid, id: Uuid, pool: &Pool<Postgres>) -> Result<(), Error> {
+        let has_variants = sqlx::query_scalar!(r#"SELECT EXISTS (SELECT 1 FROM product_variants WHERE product_id = $1) AS "exists!""#, product_id)
+            .fetch_one(pool)
+            .await?;
+        if has_variants {
+            return Err(Error::Protocol("option types cannot be removed while the product has variants".to_string()));
+        }
+
+        let delete = sqlx::query!("DELETE FROM product_option_types WHERE id = $1 AND product_id = $2", id, product_id)
+            .execute(pool)
+            .await;
+
+        match delete.unwrap_or_default().rows_affected() {
+            1 => Ok(()),
+            _ => {
+                Err(Error::RowNotFound)
+            }
+        }
+    }
+
+    async fn fetch_variants(&self, product_id: Uuid, pool: &Pool<Postgres>) -> Result<Vec<ProductVariant>, Error> {
+        let variants = sqlx::query_as!(ProductVariant, r#"
+                SELECT id, product_id, sku, price, stock, barcode, options AS "options: Json<BTreeMap<String, String>>", created_at, updated_at
+                FROM product_variants WHERE product_id = $1 ORDER BY created_at"#, product_id)
+            .fetch_all(pool)
+            .await?;
+        Ok(variants)
+    }
+
+    async fn fetch_variant(&self, product_id: Uuid, id: Uuid, pool: &Pool<Postgres>) -> Result<ProductVariant, Error> {
+        let variant = sqlx::query_as!(ProductVariant, r#"
+                SELECT id, product_id, sku, price, stock, barcode, options AS "options: Json<BTreeMap<String, String>>", created_at, updated_at
+                FROM product_variants WHERE id = $1 AND product_id = $2"#, id, product_id)
+            .fetch_one(pool)
+            .await?;
+        Ok(variant)
+    }
+
+    async fn save_variant(&self, product_id: Uuid, variant: CreateProductVariant, pool: &Pool<Postgres>) -> Result<ProductVariant, Error> {
+        let option_types = self.fetch_option_types(product_id, pool).await?;
+        if option_types.is_empty() {
+            return Err(Error::Protocol("product has no option types defined".to_string()));
+        }
+        validate_options(&variant.options, &option_types)?;
+        let sku = normalize_sku(Some(variant.sku))?
+            .ok_or_else(|| Error::Protocol("sku is required".to_string()))?;
+        let barcode = normalize_barcode(variant.barcode)?;
+
+        let now = Utc::now().timestamp_millis();
+        let variant = ProductVariant {
+            id: Uuid::new_v4(),
+            product_id,
+            sku,
+            price: variant.price,
+            stock: variant.stock,
+            barcode,
+            options: Json(variant.options),
+            created_at: now,
+            updated_at: now,
+        };
+
+        sqlx::query!(
+        "INSERT INTO product_variants (id, product_id, sku, price, stock, barcode, options, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+            variant.id,
+            variant.product_id,
+            variant.sku,
+            variant.price,
+            variant.stock,
+            variant.barcode,
+            &variant.options as _,
+            variant.created_at,
+            variant.updated_at
+        ).execute(pool).await?;
+
+        Ok(variant)
+    }
+
+    async fn update_variant(&self, product_id: Uuid, id: Uuid, request: UpdateProductVariant, pool: &Pool<Postgres>) -> Result<ProductVariant, Error> {
+        // Stok hanya boleh berubah lewat ledger stock movement
+        if request.stock.is_some() {
+            return Err(Error::Protocol("variant stock cannot be changed through an update".to_string()));
+        }
+        let mut variant = self.fetch_variant(product_id, id, pool).await?;
+
+        let mut query_builder = QueryBuilder::<Postgres>::new("UPDATE product_variants SET updated_at = ");
+        variant.updated_at = Utc::now().timestamp_millis();
+        query_builder.push_bind(variant.updated_at);
+        if let Some(sku) = normalize_sku(request.sku)? {
+            query_builder.push(", sku = ");
+            query_builder.push_bind(sku.clone());
+            variant.sku = sku;
+        }
+        if let Some(price) = request.price {
+            query_builder.push(", price = ");
+            query_builder.push_bind(price.clone());
+            variant.price = Some(price);
+        }
+        if let Some(barcode) = normalize_barcode(request.barcode)? {
+            query_builder.push(", barcode = ");
+            query_builder.push_bind(barcode.clone());
+            variant.barcode = Some(barcode);
+        }
+
+        query_builder.push(" WHERE id = ");
+        query_builder.push_bind(id);
+
+        // Melakukan update di database
+        query_builder.build().execute(pool).await?;
+        Ok(variant)
+    }
+
+    async fn delete_variant(&self, product_id: Uuid, id: Uuid, pool: &Pool<Postgres>) -> Result<(), Error> {
+        let delete = sqlx::query!("DELETE FROM product_variants WHERE id = $1 AND product_id = $2", id, product_id)
+            .execute(pool)
+            .await;
+
+        match delete.unwrap_or_default().rows_affected() {
+            1 => Ok(()),
+            _ => {
+                Err(Error::RowNotFound)
+            }
+        }
+    }
+}
@@ -1,28 +1,106 @@
-use bigdecimal::BigDecimal;
+use bytes::Bytes;
+use futures::Stream;
 use sqlx::{Error, Pool, Postgres};
 use uuid::Uuid;
-use crate::domain::category::{Category};
-use crate::domain::product::{Product, ProductReview, ProductWithReviews};
+use crate::domain::category::{Category, CategoryNode, CategoryReviewPolicy, UpdateCategoryReviewPolicy};
+use crate::domain::inventory::{CreateReservation, InventoryReservation};
+use crate::domain::product_image::{ProductImage, ReviewImage};
+use crate::domain::product_import::{ProductFileFormat, ProductImportReport};
+use crate::domain::product_price::{CreateScheduledPrice, ProductPrice};
+use crate::domain::product_variant::{CreateProductOptionType, CreateProductVariant, ProductOptionType, ProductVariant, UpdateProductVariant};
+use crate::domain::stock_movement::{CreateStockAdjustment, StockMovement, StockMovementPage, StockMovementQuery};
+use crate::domain::product::{CreateProduct, CreateProductReview, ModerationQuery, Product, ProductPage, ProductQuery, ProductReview, ProductSearchQuery, ProductSearchResult, ProductWithReviews, RejectProductReview, ReportProductReview, ReviewPage, ReviewQuery, ReviewReport, ReviewResponse, SaveReviewResponse, UpdateProduct, UpdateProductReview, VoteProductReview};
+use crate::events::Publisher;
+use crate::orders::Orders;
+use crate::services::error::ServiceError;
+use crate::storage::Storage;
 
 pub trait CategoryService {
-    async fn fetch_categories(&self, pool: &Pool<Postgres>) -> Result<Vec<Category>, Error>;
+    async fn fetch_categories(&self, include_deleted: bool, pool: &Pool<Postgres>) -> Result<Vec<Category>, Error>;
     async fn fetch_by_id(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<Category, Error>;
-    async fn save(&self, name: String, pool: &Pool<Postgres>) -> Result<Category, Error>;
-    async fn update(&self, id: Uuid, name: String, pool: &Pool<Postgres>) -> Result<Category, Error>;
+    async fn fetch_tree(&self, pool: &Pool<Postgres>) -> Result<Vec<CategoryNode>, Error>;
+    async fn fetch_subtree(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<CategoryNode, Error>;
+    async fn fetch_breadcrumb(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<Vec<Category>, Error>;
+    async fn save(&self, name: String, parent_id: Option<Uuid>, pool: &Pool<Postgres>) -> Result<Category, Error>;
+    async fn update(&self, id: Uuid, name: String, parent_id: Option<Option<Uuid>>, version: i32, pool: &Pool<Postgres>) -> Result<Category, ServiceError>;
     async fn delete(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<(), Error>;
+    async fn restore(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<Category, Error>;
+    async fn fetch_review_policy(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<CategoryReviewPolicy, Error>;
+    async fn update_review_policy(&self, id: Uuid, request: UpdateCategoryReviewPolicy, pool: &Pool<Postgres>) -> Result<CategoryReviewPolicy, Error>;
 }
 
 pub trait ProductService {
-    async fn fetch_all(&self, pool: &Pool<Postgres>) -> Result<Vec<Product>, Error>;
+    async fn fetch_all(&self, query: ProductQuery, pool: &Pool<Postgres>) -> Result<ProductPage, Error>;
+    async fn fetch_low_stock(&self, pool: &Pool<Postgres>) -> Result<Vec<Product>, Error>;
     async fn fetch_by_id(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<Product, Error>;
+    async fn fetch_by_slug(&self, slug: &str, pool: &Pool<Postgres>) -> Result<Product, Error>;
+    async fn fetch_by_sku(&self, sku: &str, pool: &Pool<Postgres>) -> Result<Product, Error>;
+    async fn search(&self, query: ProductSearchQuery, pool: &Pool<Postgres>) -> Result<ProductSearchResult, Error>;
     async fn fetch_by_id_with_reviews(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<ProductWithReviews, Error>;
-    async fn save(&self, name: String, description: Option<String>, price: BigDecimal, stock: i32, category_id: Uuid, pool: &Pool<Postgres>) -> Result<Product, Error>;
-    async fn update(&self, id: Uuid, name: Option<String>, description: Option<String>, price: Option<BigDecimal>, stock: Option<i32>, pool: &Pool<Postgres>) -> Result<Product, Error>;
+    async fn save(&self, request: CreateProduct, pool: &Pool<Postgres>) -> Result<Product, Error>;
+    async fn update(&self, id: Uuid, actor_id: Uuid, request: UpdateProduct, version: i32, pool: &Pool<Postgres>) -> Result<Product, ServiceError>;
     async fn delete(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<(), Error>;
+    async fn restore(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<Product, Error>;
+}
 
+pub trait ProductImportService {
+    async fn import(&self, data: Bytes, format: ProductFileFormat, dry_run: bool, actor: &str, pool: &Pool<Postgres>) -> Result<ProductImportReport, ServiceError>;
+    fn export(&self, format: ProductFileFormat, include_deleted: bool, pool: Pool<Postgres>) -> impl Stream<Item = Result<Bytes, Error>> + Send + 'static;
 }
 
 pub trait ProductReviewService {
-    async fn save(&self, product_id: Uuid, user_id: Uuid, comment: Option<String>, rating: Option<i32>, pool: &Pool<Postgres>) -> Result<ProductReview, Error>;
+    async fn fetch_reviews(&self, product_id: Uuid, query: ReviewQuery, pool: &Pool<Postgres>) -> Result<ReviewPage, Error>;
+    async fn save(&self, product_id: Uuid, user_id: Uuid, request: CreateProductReview, order_client: &Orders, pool: &Pool<Postgres>) -> Result<ProductReview, ServiceError>;
+    async fn update(&self, product_id: Uuid, id: Uuid, user_id: Uuid, request: UpdateProductReview, pool: &Pool<Postgres>) -> Result<ProductReview, ServiceError>;
+    async fn delete(&self, product_id: Uuid, id: Uuid, user_id: Uuid, storage: &Storage, pool: &Pool<Postgres>) -> Result<(), ServiceError>;
+    async fn fetch_moderation_queue(&self, query: ModerationQuery, pool: &Pool<Postgres>) -> Result<ReviewPage, Error>;
+    async fn approve(&self, product_id: Uuid, id: Uuid, moderator: Uuid, pool: &Pool<Postgres>) -> Result<ProductReview, ServiceError>;
+    async fn reject(&self, product_id: Uuid, id: Uuid, moderator: Uuid, request: RejectProductReview, pool: &Pool<Postgres>) -> Result<ProductReview, ServiceError>;
+    async fn report(&self, product_id: Uuid, id: Uuid, user_id: Uuid, request: ReportProductReview, pool: &Pool<Postgres>) -> Result<ReviewReport, ServiceError>;
+    async fn vote(&self, product_id: Uuid, id: Uuid, user_id: Uuid, request: VoteProductReview, pool: &Pool<Postgres>) -> Result<ProductReview, ServiceError>;
+    async fn remove_vote(&self, product_id: Uuid, id: Uuid, user_id: Uuid, pool: &Pool<Postgres>) -> Result<ProductReview, ServiceError>;
+    async fn save_image(&self, product_id: Uuid, id: Uuid, user_id: Uuid, data: Bytes, storage: &Storage, pool: &Pool<Postgres>) -> Result<ReviewImage, ServiceError>;
+    async fn delete_image(&self, product_id: Uuid, id: Uuid, image_id: Uuid, user_id: Uuid, storage: &Storage, pool: &Pool<Postgres>) -> Result<(), ServiceError>;
+    async fn save_response(&self, product_id: Uuid, id: Uuid, responder_id: Uuid, request: SaveReviewResponse, pool: &Pool<Postgres>) -> Result<ReviewResponse, ServiceError>;
+    async fn update_response(&self, product_id: Uuid, id: Uuid, responder_id: Uuid, request: SaveReviewResponse, pool: &Pool<Postgres>) -> Result<ReviewResponse, ServiceError>;
+    async fn delete_response(&self, product_id: Uuid, id: Uuid, pool: &Pool<Postgres>) -> Result<(), ServiceError>;
+}
+
+pub trait ProductVariantService {
+    async fn fetch_option_types(&self, product_id: Uuid, pool: &Pool<Postgres>) -> Result<Vec<ProductOptionType>, Error>;
+    async fn save_option_type(&self, product_id: Uuid, option_type: CreateProductOptionType, pool: &Pool<Postgres>) -> Result<ProductOptionType, Error>;
+    async fn delete_option_type(&self, product_id: Uuid, id: Uuid, pool: &Pool<Postgres>) -> Result<(), Error>;
+    async fn fetch_variants(&self, product_id: Uuid, pool: &Pool<Postgres>) -> Result<Vec<ProductVariant>, Error>;
+    async fn fetch_variant(&self, product_id: Uuid, id: Uuid, pool: &Pool<Postgres>) -> Result<ProductVariant, Error>;
+    async fn save_variant(&self, product_id: Uuid, variant: CreateProductVariant, pool: &Pool<Postgres>) -> Result<ProductVariant, Error>;
+    async fn update_variant(&self, product_id: Uuid, id: Uuid, variant: UpdateProductVariant, pool: &Pool<Postgres>) -> Result<ProductVariant, Error>;
+    async fn delete_variant(&self, product_id: Uuid, id: Uuid, pool: &Pool<Postgres>) -> Result<(), Error>;
+}
+
+pub trait ProductImageService {
+    async fn fetch_images(&self, product_id: Uuid, pool: &Pool<Postgres>) -> Result<Vec<ProductImage>, Error>;
+    async fn save_image(&self, product_id: Uuid, data: Bytes, storage: &Storage, thumbnail_sizes: &[u32], pool: &Pool<Postgres>) -> Result<ProductImage, Error>;
+    async fn reorder(&self, product_id: Uuid, image_ids: Vec<Uuid>, pool: &Pool<Postgres>) -> Result<Vec<ProductImage>, Error>;
+    async fn set_primary(&self, product_id: Uuid, id: Uuid, pool: &Pool<Postgres>) -> Result<Vec<ProductImage>, Error>;
+    async fn delete_image(&self, product_id: Uuid, id: Uuid, storage: &Storage, pool: &Pool<Postgres>) -> Result<(), Error>;
+}
+
+pub trait InventoryService {
+    async fn reserve(&self, request: CreateReservation, pool: &Pool<Postgres>) -> Result<InventoryReservation, ServiceError>;
+    async fn fetch_reservation(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<InventoryReservation, Error>;
+    async fn confirm(&self, id: Uuid, publisher: &Publisher, pool: &Pool<Postgres>) -> Result<InventoryReservation, ServiceError>;
+    async fn release(&self, id: Uuid, pool: &Pool<Postgres>) -> Result<InventoryReservation, Error>;
+    async fn expire_reservations(&self, pool: &Pool<Postgres>) -> Result<Vec<Uuid>, Error>;
+}
+
+pub trait StockMovementService {
+    async fn adjust(&self, product_id: Uuid, actor_id: Uuid, request: CreateStockAdjustment, publisher: &Publisher, pool: &Pool<Postgres>) -> Result<StockMovement, ServiceError>;
+    async fn fetch_movements(&self, product_id: Uuid, query: StockMovementQuery, pool: &Pool<Postgres>) -> Result<StockMovementPage, Error>;
+}
 
+pub trait ProductPriceService {
+    async fn fetch_price_history(&self, product_id: Uuid, pool: &Pool<Postgres>) -> Result<Vec<ProductPrice>, Error>;
+    async fn schedule_price(&self, product_id: Uuid, actor_id: Uuid, request: CreateScheduledPrice, pool: &Pool<Postgres>) -> Result<ProductPrice, Error>;
+    async fn cancel_scheduled_price(&self, product_id: Uuid, id: Uuid, pool: &Pool<Postgres>) -> Result<(), Error>;
+    async fn apply_scheduled_prices(&self, pool: &Pool<Postgres>) -> Result<Vec<Uuid>, Error>;
 }
\ No newline at end of file
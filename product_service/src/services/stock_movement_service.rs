@@ -0,0 +1,186 @@
+use sqlx::{Error, PgConnection, Pool, Postgres};
+use sqlx::types::chrono::Utc;
+use uuid::Uuid;
+use common::pagination::{decode_cursor, encode_cursor};
+use crate::domain::event::{LowStockEvent, ProductEvent};
+use crate::domain::stock_movement::{CreateStockAdjustment, StockMovement, StockMovementCursor, StockMovementPage, StockMovementQuery, StockMovementReason};
+use crate::events::{EventPublisher, Publisher};
+use crate::services::error::ServiceError;
+use crate::services::service::StockMovementService;
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+
+pub struct StockMovementServiceImpl;
+
+fn validate_direction(reason: StockMovementReason, quantity: i32) -> Result<(), Error> {
+    match reason {
+        StockMovementReason::Restock | StockMovementReason::Return if quantity <= 0 => {
+            Err(Error::Protocol("restock and return quantities must be positive".to_string()))
+        }
+        StockMovementReason::Sale | StockMovementReason::Damage if quantity >= 0 => {
+            Err(Error::Protocol("sale and damage quantities must be negative".to_string()))
+        }
+        StockMovementReason::Correction if quantity == 0 => {
+            Err(Error::Protocol("correction quantity must not be zero".to_string()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Satu-satunya jalan untuk mengubah `products.stock`: stok diubah dan dicatat ke ledger
+/// dalam transaksi yang sama, jadi stok selalu sama dengan total pergerakan.
+/// Event low stock dikembalikan kalau perubahan ini melewati reorder threshold,
+/// pemanggil yang mem-publish setelah commit.
+pub async fn record_movement(
+    conn: &mut PgConnection,
+    product_id: Uuid,
+    reason: StockMovementReason,
+    quantity: i32,
+    actor: &str,
+    reference: Option<&str>,
+) -> Result<(StockMovement, Option<ProductEvent>), ServiceError> {
+    let now = Utc::now().timestamp_millis();
+
+    let updated = sqlx::query!(
+        "UPDATE products SET stock = stock + $1, version = version + 1, updated_at = $2 WHERE id = $3 AND stock + $1 >= 0 RETURNING name, stock, reorder_threshold",
+        quantity,
+        now,
+        product_id
+    )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+    let Some(updated) = updated else {
+        let available = sqlx::query_scalar!("SELECT stock FROM products WHERE id = $1", product_id)
+            .fetch_one(&mut *conn)
+            .await?;
+        return Err(ServiceError::InsufficientStock { requested: -quantity, available });
+    };
+
+    let movement = StockMovement {
+        id: Uuid::new_v4(),
+        product_id,
+        reason,
+        quantity,
+        balance_after: updated.stock,
+        actor: actor.to_string(),
+        reference: reference.map(str::to_string),
+        created_at: now,
+    };
+    insert_movement(conn, &movement).await?;
+
+    let balance_before = updated.stock - quantity;
+    let crossed = updated.reorder_threshold > 0
+        && balance_before > updated.reorder_threshold
+        && updated.stock <= updated.reorder_threshold;
+    let event = crossed.then_some(ProductEvent::LowStock(LowStockEvent {
+        product_id,
+        product_name: updated.name,
+        stock: updated.stock,
+        reorder_threshold: updated.reorder_threshold,
+        occurred_at: now,
+    }));
+
+    Ok((movement, event))
+}
+
+pub async fn insert_movement(conn: &mut PgConnection, movement: &StockMovement) -> Result<(), Error> {
+    sqlx::query!(
+    "INSERT INTO stock_movements (id, product_id, reason, quantity, balance_after, actor, reference, created_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+        movement.id,
+        movement.product_id,
+        movement.reason as _,
+        movement.quantity,
+        movement.balance_after,
+        movement.actor,
+        movement.reference,
+        movement.created_at
+    ).execute(&mut *conn).await?;
+
+    Ok(())
+}
+
+impl StockMovementService for StockMovementServiceImpl {
+    async fn adjust(&self, product_id: Uuid, actor_id: Uuid, request: CreateStockAdjustment, publisher: &Publisher, pool: &Pool<Postgres>) -> Result<StockMovement, ServiceError> {
+        validate_direction(request.reason, request.quantity)?;
+
+        let mut tx = pool.begin().await?;
+
+        // Lock baris produk seperti saat reserve, supaya penyesuaian dan reservasi paralel tidak saling melewati
+        let product = sqlx::query!(r#"SELECT stock, deleted_at IS NOT NULL AS "archived!" FROM products WHERE id = $1 FOR UPDATE"#, product_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        if product.archived {
+            return Err(Error::Protocol("stock of an archived product cannot be adjusted".to_string()).into());
+        }
+
+        // Unit yang sudah ditahan reservasi aktif tidak boleh ikut dikurangi, kalau tidak confirm reservasi itu akan gagal.
+        // Pengurangan dari confirm sendiri lewat record_movement langsung dan hanya dibatasi stock.
+        if request.quantity < 0 {
+            let reserved = sqlx::query_scalar!(r#"
+                    SELECT COALESCE(SUM(quantity), 0)::integer AS "reserved!" FROM inventory_reservations
+                    WHERE product_id = $1 AND status = 'active' AND expires_at > $2"#, product_id, Utc::now().timestamp_millis())
+                .fetch_one(&mut *tx)
+                .await?;
+            let available = product.stock - reserved;
+            if -request.quantity > available {
+                return Err(ServiceError::InsufficientStock { requested: -request.quantity, available: available.max(0) });
+            }
+        }
+
+        let (movement, event) = record_movement(&mut tx, product_id, request.reason, request.quantity, &actor_id.to_string(), request.reference.as_deref()).await?;
+
+        tx.commit().await?;
+        if let Some(event) = event {
+            publisher.publish(event).await;
+        }
+        Ok(movement)
+    }
+
+    async fn fetch_movements(&self, product_id: Uuid, query: StockMovementQuery, pool: &Pool<Postgres>) -> Result<StockMovementPage, Error> {
+        let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        let cursor = match &query.cursor {
+            Some(cursor) => Some(decode_cursor::<StockMovementCursor>(cursor)
+                .ok_or_else(|| Error::Protocol("invalid cursor".to_string()))?),
+            None => None,
+        };
+
+        let product_exists = sqlx::query_scalar!(r#"SELECT EXISTS (SELECT 1 FROM products WHERE id = $1) AS "exists!""#, product_id)
+            .fetch_one(pool)
+            .await?;
+        if !product_exists {
+            return Err(Error::RowNotFound);
+        }
+
+        // Terbaru dulu, keyset di (created_at, id)
+        let mut movements = sqlx::query_as!(StockMovement, r#"
+                SELECT id, product_id, reason AS "reason: StockMovementReason", quantity, balance_after, actor, reference, created_at
+                FROM stock_movements
+                WHERE product_id = $1 AND ($2::bigint IS NULL OR (created_at, id) < ($2, $3))
+                ORDER BY created_at DESC, id DESC
+                LIMIT $4"#,
+            product_id,
+            cursor.as_ref().map(|cursor| cursor.created_at),
+            cursor.as_ref().map(|cursor| cursor.id),
+            limit + 1)
+            .fetch_all(pool)
+            .await?;
+
+        let has_more = movements.len() as i64 > limit;
+        movements.truncate(limit as usize);
+        let next_cursor = movements.last()
+            .filter(|_| has_more)
+            .map(|movement| encode_cursor(&StockMovementCursor { created_at: movement.created_at, id: movement.id }));
+
+        let total = sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM stock_movements WHERE product_id = $1"#, product_id)
+            .fetch_one(pool)
+            .await?;
+
+        Ok(StockMovementPage {
+            movements,
+            next_cursor,
+            total,
+        })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,44 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use bytes::Bytes;
+use crate::storage::ObjectStorage;
+
+#[derive(Clone)]
+pub struct LocalStorage {
+    root: PathBuf,
+    public_url: String,
+}
+
+impl LocalStorage {
+    pub fn new(root: &str, public_url: &str) -> Self {
+        LocalStorage {
+            root: PathBuf::from(root),
+            public_url: public_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl ObjectStorage for LocalStorage {
+    async fn put(&self, key: &str, data: Bytes, _content_type: &str) -> io::Result<()> {
+        let path = self.root.join(key);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(path, data).await
+    }
+
+    async fn delete(&self, key: &str) -> io::Result<()> {
+        match tokio::fs::remove_file(self.root.join(key)).await {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+            _ => Ok(()),
+        }
+    }
+
+    fn url(&self, key: &str) -> String {
+        format!("{}/{}", self.public_url, key)
+    }
+}
\ No newline at end of file
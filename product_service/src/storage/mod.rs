@@ -0,0 +1,52 @@
+pub mod local;
+pub mod s3;
+
+use std::io;
+use bytes::Bytes;
+use crate::config::StorageConfig;
+use crate::storage::local::LocalStorage;
+use crate::storage::s3::S3Storage;
+
+pub trait ObjectStorage {
+    async fn put(&self, key: &str, data: Bytes, content_type: &str) -> io::Result<()>;
+    async fn delete(&self, key: &str) -> io::Result<()>;
+    fn url(&self, key: &str) -> String;
+}
+
+#[derive(Clone)]
+pub enum Storage {
+    Local(LocalStorage),
+    S3(S3Storage),
+}
+
+impl ObjectStorage for Storage {
+    async fn put(&self, key: &str, data: Bytes, content_type: &str) -> io::Result<()> {
+        match self {
+            Storage::Local(storage) => storage.put(key, data, content_type).await,
+            Storage::S3(storage) => storage.put(key, data, content_type).await,
+        }
+    }
+
+    async fn delete(&self, key: &str) -> io::Result<()> {
+        match self {
+            Storage::Local(storage) => storage.delete(key).await,
+            Storage::S3(storage) => storage.delete(key).await,
+        }
+    }
+
+    fn url(&self, key: &str) -> String {
+        match self {
+            Storage::Local(storage) => storage.url(key),
+            Storage::S3(storage) => storage.url(key),
+        }
+    }
+}
+
+pub fn create_storage(config: &StorageConfig) -> Result<Storage, object_store::Error> {
+    match config {
+        StorageConfig::S3 { bucket, endpoint, region, access_key, secret_key, public_url } => Ok(Storage::S3(
+            S3Storage::new(bucket, endpoint, region, access_key, secret_key, public_url)?
+        )),
+        StorageConfig::Local { path, public_url } => Ok(Storage::Local(LocalStorage::new(path, public_url))),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,66 @@
+use std::io;
+use bytes::Bytes;
+use object_store::aws::{AmazonS3, AmazonS3Builder};
+use object_store::path::Path;
+use object_store::{Attribute, Attributes, ObjectStore, PutOptions, PutPayload};
+use crate::storage::ObjectStorage;
+
+#[derive(Clone)]
+pub struct S3Storage {
+    store: AmazonS3,
+    public_url: String,
+}
+
+impl S3Storage {
+    pub fn new(bucket: &str, endpoint: &str, region: &str, access_key: &str, secret_key: &str, public_url: &str) -> Result<Self, object_store::Error> {
+        let mut builder = AmazonS3Builder::new()
+            .with_bucket_name(bucket)
+            .with_region(region)
+            .with_access_key_id(access_key)
+            .with_secret_access_key(secret_key);
+        // Endpoint diisi untuk server S3-compatible seperti MinIO
+        if !endpoint.is_empty() {
+            builder = builder
+                .with_endpoint(endpoint)
+                .with_allow_http(endpoint.starts_with("http://"));
+        }
+
+        let public_url = if public_url.is_empty() {
+            format!("{}/{}", endpoint.trim_end_matches('/'), bucket)
+        } else {
+            public_url.trim_end_matches('/').to_string()
+        };
+
+        Ok(S3Storage {
+            store: builder.build()?,
+            public_url,
+        })
+    }
+}
+
+impl ObjectStorage for S3Storage {
+    async fn put(&self, key: &str, data: Bytes, content_type: &str) -> io::Result<()> {
+        let mut attributes = Attributes::new();
+        attributes.insert(Attribute::ContentType, content_type.to_string().into());
+        let options = PutOptions {
+            attributes,
+            ..Default::default()
+        };
+
+        self.store.put_opts(&Path::from(key), PutPayload::from(data), options)
+            .await
+            .map(|_| ())
+            .map_err(io::Error::other)
+    }
+
+    async fn delete(&self, key: &str) -> io::Result<()> {
+        match self.store.delete(&Path::from(key)).await {
+            Err(object_store::Error::NotFound { .. }) | Ok(()) => Ok(()),
+            Err(error) => Err(io::Error::other(error)),
+        }
+    }
+
+    fn url(&self, key: &str) -> String {
+        format!("{}/{}", self.public_url, key)
+    }
+}
\ No newline at end of file